@@ -0,0 +1,308 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_autoscaling::types::RefreshPreferences;
+use aws_sdk_autoscaling::Client as AutoScalingClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use super::ec2::{get_instances_by_id, Ec2Instance};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoScalingGroup {
+    pub name: String,
+    pub arn: Option<String>,
+    pub min_size: i32,
+    pub max_size: i32,
+    pub desired_capacity: i32,
+    /// Launch template name, or launch configuration name for older groups
+    pub launch_template: Option<String>,
+    pub health_check_type: Option<String>,
+    /// Set while the group is being deleted
+    pub status: Option<String>,
+    pub instances: Vec<AsgInstance>,
+    /// Most recent instance refresh
+    pub instance_refresh: Option<AsgInstanceRefresh>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsgInstance {
+    pub instance_id: String,
+    /// Pending, InService, Standby, Terminating, ...
+    pub lifecycle_state: String,
+    pub health_status: Option<String>,
+    pub availability_zone: Option<String>,
+    pub protected_from_scale_in: bool,
+    /// Matching EC2 instance (for opening an SSM session)
+    pub ec2_instance: Option<Ec2Instance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsgInstanceRefresh {
+    pub id: String,
+    /// Pending, InProgress, Successful, Failed, Cancelling, Cancelled, ...
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub percentage_complete: Option<i32>,
+    pub instances_to_update: Option<i32>,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+}
+
+/// Instance refresh lookups requested at once
+const REFRESH_CONCURRENCY: usize = 8;
+
+/// Create an Auto Scaling client with the specified profile and region
+async fn create_autoscaling_client(
+    profile: &str,
+    region: &str,
+) -> Result<AutoScalingClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(AutoScalingClient::new(&config))
+}
+
+/// Get the most recent instance refresh of a group
+async fn get_latest_instance_refresh(
+    client: &AutoScalingClient,
+    group_name: &str,
+) -> Result<Option<AsgInstanceRefresh>, String> {
+    let result = client
+        .describe_instance_refreshes()
+        .auto_scaling_group_name(group_name)
+        .max_records(1)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get instance refreshes: {}", e))?;
+
+    Ok(result
+        .instance_refreshes()
+        .first()
+        .map(|r| AsgInstanceRefresh {
+            id: r.instance_refresh_id().unwrap_or_default().to_string(),
+            status: r
+                .status()
+                .map(|s| s.as_str().to_string())
+                .unwrap_or_default(),
+            status_reason: r.status_reason().map(|s| s.to_string()),
+            percentage_complete: r.percentage_complete(),
+            instances_to_update: r.instances_to_update(),
+            started_at: r.start_time().map(|d| (d.as_secs_f64() * 1000.0) as i64),
+            finished_at: r.end_time().map(|d| (d.as_secs_f64() * 1000.0) as i64),
+        }))
+}
+
+/// List Auto Scaling groups with their instances and latest instance refresh
+pub async fn list_auto_scaling_groups(
+    profile: &str,
+    region: &str,
+) -> Result<Vec<AutoScalingGroup>, String> {
+    let client = create_autoscaling_client(profile, region).await?;
+
+    let mut groups = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_auto_scaling_groups();
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list Auto Scaling groups: {}", e))?;
+
+        for group in result.auto_scaling_groups() {
+            let launch_template = group
+                .launch_template()
+                .and_then(|t| t.launch_template_name())
+                .or_else(|| {
+                    group
+                        .mixed_instances_policy()
+                        .and_then(|p| p.launch_template())
+                        .and_then(|t| t.launch_template_specification())
+                        .and_then(|t| t.launch_template_name())
+                })
+                .or(group.launch_configuration_name())
+                .map(|s| s.to_string());
+
+            groups.push(AutoScalingGroup {
+                name: group
+                    .auto_scaling_group_name()
+                    .unwrap_or_default()
+                    .to_string(),
+                arn: group.auto_scaling_group_arn().map(|s| s.to_string()),
+                min_size: group.min_size().unwrap_or_default(),
+                max_size: group.max_size().unwrap_or_default(),
+                desired_capacity: group.desired_capacity().unwrap_or_default(),
+                launch_template,
+                health_check_type: group.health_check_type().map(|s| s.to_string()),
+                status: group.status().map(|s| s.to_string()),
+                instances: group
+                    .instances()
+                    .iter()
+                    .map(|i| AsgInstance {
+                        instance_id: i.instance_id().unwrap_or_default().to_string(),
+                        lifecycle_state: i
+                            .lifecycle_state()
+                            .map(|s| s.as_str().to_string())
+                            .unwrap_or_default(),
+                        health_status: i.health_status().map(|s| s.to_string()),
+                        availability_zone: i.availability_zone().map(|s| s.to_string()),
+                        protected_from_scale_in: i.protected_from_scale_in().unwrap_or(false),
+                        ec2_instance: None,
+                    })
+                    .collect(),
+                instance_refresh: None,
+            });
+        }
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(REFRESH_CONCURRENCY));
+    let mut handles = Vec::new();
+    for (index, group) in groups.iter().enumerate() {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let group_name = group.name.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .map_err(|e| format!("Failed to schedule instance refresh request: {}", e))?;
+            let refresh = get_latest_instance_refresh(&client, &group_name)
+                .await
+                .map_err(|e| format!("{} ({})", e, group_name))?;
+            Ok::<_, String>((index, refresh))
+        }));
+    }
+
+    for handle in handles {
+        let result = handle
+            .await
+            .map_err(|e| format!("Instance refresh request failed: {}", e))
+            .and_then(|r| r);
+        match result {
+            Ok((index, refresh)) => groups[index].instance_refresh = refresh,
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    let instance_ids: Vec<String> = groups
+        .iter()
+        .flat_map(|g| g.instances.iter().map(|i| i.instance_id.clone()))
+        .collect();
+
+    match get_instances_by_id(profile, region, &instance_ids).await {
+        Ok(instances) => {
+            let by_id: HashMap<String, Ec2Instance> = instances
+                .into_iter()
+                .map(|i| (i.instance_id.clone(), i))
+                .collect();
+            for instance in groups.iter_mut().flat_map(|g| g.instances.iter_mut()) {
+                instance.ec2_instance = by_id.get(&instance.instance_id).cloned();
+            }
+        }
+        // The group list is still useful without EC2 details
+        Err(e) => tracing::warn!("Failed to describe Auto Scaling instances: {}", e),
+    }
+
+    groups.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(groups)
+}
+
+/// Change the desired capacity of a group
+pub async fn set_desired_capacity(
+    profile: &str,
+    region: &str,
+    group_name: &str,
+    desired_capacity: i32,
+    honor_cooldown: bool,
+) -> Result<(), String> {
+    let client = create_autoscaling_client(profile, region).await?;
+
+    client
+        .set_desired_capacity()
+        .auto_scaling_group_name(group_name)
+        .desired_capacity(desired_capacity)
+        .honor_cooldown(honor_cooldown)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to set desired capacity: {}", e))?;
+
+    Ok(())
+}
+
+/// Move instances into or out of Standby. Instances in Standby stay running but
+/// are taken out of service and never replaced, so they can be debugged.
+pub async fn set_instance_standby(
+    profile: &str,
+    region: &str,
+    group_name: &str,
+    instance_ids: Vec<String>,
+    standby: bool,
+    decrement_desired_capacity: bool,
+) -> Result<(), String> {
+    let client = create_autoscaling_client(profile, region).await?;
+
+    if standby {
+        client
+            .enter_standby()
+            .auto_scaling_group_name(group_name)
+            .set_instance_ids(Some(instance_ids))
+            .should_decrement_desired_capacity(decrement_desired_capacity)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to put instances into standby: {}", e))?;
+    } else {
+        client
+            .exit_standby()
+            .auto_scaling_group_name(group_name)
+            .set_instance_ids(Some(instance_ids))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to take instances out of standby: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Start an instance refresh, returning its ID
+pub async fn start_instance_refresh(
+    profile: &str,
+    region: &str,
+    group_name: &str,
+    min_healthy_percentage: Option<i32>,
+    instance_warmup_secs: Option<i32>,
+) -> Result<String, String> {
+    let client = create_autoscaling_client(profile, region).await?;
+
+    let result = client
+        .start_instance_refresh()
+        .auto_scaling_group_name(group_name)
+        .preferences(
+            RefreshPreferences::builder()
+                .set_min_healthy_percentage(min_healthy_percentage)
+                .set_instance_warmup(instance_warmup_secs)
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to start instance refresh: {}", e))?;
+
+    result
+        .instance_refresh_id()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "No instance refresh ID returned".to_string())
+}
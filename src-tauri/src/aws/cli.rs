@@ -0,0 +1,33 @@
+use tokio::process::Command;
+
+/// Version of the installed AWS CLI
+pub async fn aws_cli_version() -> Result<String, String> {
+    let output = Command::new("aws")
+        .arg("--version")
+        .output()
+        .await
+        .map_err(|e| format!("Failed to execute AWS CLI: {}", e))?;
+
+    if output.status.success() {
+        let version = String::from_utf8_lossy(&output.stdout).to_string();
+        Ok(version.trim().to_string())
+    } else {
+        Err("AWS CLI not found or not working properly".to_string())
+    }
+}
+
+/// Version of the installed Session Manager plugin
+pub async fn ssm_plugin_version() -> Result<String, String> {
+    let output = Command::new("session-manager-plugin")
+        .arg("--version")
+        .output()
+        .await
+        .map_err(|_| "session-manager-plugin not found. Please install it from: https://docs.aws.amazon.com/systems-manager/latest/userguide/session-manager-working-with-install-plugin.html".to_string())?;
+
+    if output.status.success() {
+        let version = String::from_utf8_lossy(&output.stdout).to_string();
+        Ok(version.trim().to_string())
+    } else {
+        Err("session-manager-plugin not working properly".to_string())
+    }
+}
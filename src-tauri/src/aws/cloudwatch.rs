@@ -178,7 +178,6 @@ pub async fn get_log_events(
 }
 
 /// Get log events from a specific log stream
-#[allow(dead_code)]
 pub async fn get_log_stream_events(
     profile: &str,
     region: &str,
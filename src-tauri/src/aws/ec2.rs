@@ -1,10 +1,12 @@
 use aws_config::BehaviorVersion;
 use aws_sdk_ec2::Client as Ec2Client;
+use aws_sdk_ssm::types::InstanceInformationStringFilter;
 use aws_sdk_ssm::Client as SsmClient;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Ec2Instance {
     pub instance_id: String,
     pub name: Option<String>,
@@ -13,8 +15,102 @@ pub struct Ec2Instance {
     pub private_ip: Option<String>,
     pub public_ip: Option<String>,
     pub platform: Option<String>,
+    /// Launched with hibernation enabled, so it can be hibernated instead of stopped
+    pub hibernation_configured: bool,
     pub ssm_enabled: bool,
     pub ssm_ping_status: Option<String>,
+    pub ssm_agent_version: Option<String>,
+    /// SSM reports a newer agent release than the installed one
+    pub ssm_agent_outdated: bool,
+    /// OS as reported by the SSM agent
+    pub ssm_platform_name: Option<String>,
+    pub ssm_platform_version: Option<String>,
+    pub ssm_last_ping_at: Option<i64>,
+    /// Status of the last State Manager association run
+    pub ssm_association_status: Option<String>,
+    /// IP address the SSM agent reports
+    pub ssm_ip_address: Option<String>,
+    pub vpc_id: Option<String>,
+    pub subnet_id: Option<String>,
+    pub tags: HashMap<String, String>,
+}
+
+/// Instances with a tag, optionally restricted to some values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagFilter {
+    pub key: String,
+    /// Any value matches when empty
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+/// Server-side filters for listing instances
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InstanceFilters {
+    pub tags: Vec<TagFilter>,
+    pub vpc_ids: Vec<String>,
+    pub subnet_ids: Vec<String>,
+    pub image_ids: Vec<String>,
+    /// Include instances in every state, not only running ones
+    pub all_states: bool,
+}
+
+impl InstanceFilters {
+    /// Convert to DescribeInstances filters
+    fn to_ec2_filters(&self) -> Vec<aws_sdk_ec2::types::Filter> {
+        let filter = |name: String, values: &[String]| {
+            aws_sdk_ec2::types::Filter::builder()
+                .name(name)
+                .set_values(Some(values.to_vec()))
+                .build()
+        };
+
+        let mut filters: Vec<_> = self
+            .tags
+            .iter()
+            .map(|tag| {
+                if tag.values.is_empty() {
+                    filter("tag-key".to_string(), std::slice::from_ref(&tag.key))
+                } else {
+                    filter(format!("tag:{}", tag.key), &tag.values)
+                }
+            })
+            .collect();
+
+        for (name, values) in [
+            ("vpc-id", &self.vpc_ids),
+            ("subnet-id", &self.subnet_ids),
+            ("image-id", &self.image_ids),
+        ] {
+            if !values.is_empty() {
+                filters.push(filter(name.to_string(), values));
+            }
+        }
+
+        // Only get running instances unless every state was asked for
+        if !self.all_states {
+            filters.push(filter(
+                "instance-state-name".to_string(),
+                &["running".to_string()],
+            ));
+        }
+
+        filters
+    }
+}
+
+/// Instances sharing a tag value, possibly split further by the next tag key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceGroup {
+    pub tag_key: String,
+    /// None for instances without the tag
+    pub value: Option<String>,
+    pub instance_count: usize,
+    /// Subgroups by the next tag key, empty at the last level
+    pub groups: Vec<InstanceGroup>,
+    /// Instances of the group, only filled at the last level
+    pub instances: Vec<Ec2Instance>,
 }
 
 /// Create an EC2 client with the specified profile and region
@@ -39,18 +135,53 @@ async fn create_ssm_client(profile: &str, region: &str) -> Result<SsmClient, Str
     Ok(SsmClient::new(&config))
 }
 
-/// Get all SSM-managed instance IDs
-async fn get_ssm_managed_instances(
+/// SSM agent details of a managed instance
+#[derive(Debug, Clone, Default)]
+struct SsmInstanceInfo {
+    ping_status: Option<String>,
+    agent_version: Option<String>,
+    is_latest_version: Option<bool>,
+    platform_name: Option<String>,
+    platform_version: Option<String>,
+    last_ping_at: Option<i64>,
+    association_status: Option<String>,
+    ip_address: Option<String>,
+}
+
+/// Most values an EC2 `DescribeInstances` filter accepts
+const EC2_FILTER_VALUES_LIMIT: usize = 200;
+
+/// Most values the SSM `InstanceIds` instance information filter accepts
+const SSM_INSTANCE_IDS_LIMIT: usize = 50;
+
+/// Get SSM-managed instances and their agent details in one pass, all of them when
+/// `instance_ids` is empty (at most `SSM_INSTANCE_IDS_LIMIT` IDs otherwise)
+async fn get_ssm_instance_info(
     profile: &str,
     region: &str,
-) -> Result<HashSet<String>, String> {
+    instance_ids: &[String],
+) -> Result<HashMap<String, SsmInstanceInfo>, String> {
     let client = create_ssm_client(profile, region).await?;
 
-    let mut instance_ids = HashSet::new();
+    let filter = if instance_ids.is_empty() {
+        None
+    } else {
+        Some(
+            InstanceInformationStringFilter::builder()
+                .key("InstanceIds")
+                .set_values(Some(instance_ids.to_vec()))
+                .build()
+                .map_err(|e| format!("Failed to build SSM instance filter: {}", e))?,
+        )
+    };
+
+    let mut instances = HashMap::new();
     let mut next_token: Option<String> = None;
 
     loop {
-        let mut request = client.describe_instance_information();
+        let mut request = client
+            .describe_instance_information()
+            .set_filters(filter.clone().map(|f| vec![f]));
 
         if let Some(token) = next_token {
             request = request.next_token(token);
@@ -62,9 +193,25 @@ async fn get_ssm_managed_instances(
             .map_err(|e| format!("Failed to get SSM instances: {}", e))?;
 
         for info in result.instance_information_list() {
-            if let Some(id) = info.instance_id() {
-                instance_ids.insert(id.to_string());
-            }
+            let Some(id) = info.instance_id() else {
+                continue;
+            };
+
+            instances.insert(
+                id.to_string(),
+                SsmInstanceInfo {
+                    ping_status: info.ping_status().map(|s| s.as_str().to_string()),
+                    agent_version: info.agent_version().map(|s| s.to_string()),
+                    is_latest_version: info.is_latest_version(),
+                    platform_name: info.platform_name().map(|s| s.to_string()),
+                    platform_version: info.platform_version().map(|s| s.to_string()),
+                    last_ping_at: info
+                        .last_ping_date_time()
+                        .map(|d| (d.as_secs_f64() * 1000.0) as i64),
+                    association_status: info.association_status().map(|s| s.to_string()),
+                    ip_address: info.ip_address().map(|s| s.to_string()),
+                },
+            );
         }
 
         next_token = result.next_token().map(|s| s.to_string());
@@ -73,72 +220,89 @@ async fn get_ssm_managed_instances(
         }
     }
 
-    Ok(instance_ids)
+    Ok(instances)
 }
 
-/// Get SSM ping status for instances
-async fn get_ssm_ping_status(
+/// List EC2 instances with SSM status matching `filters`
+pub async fn list_instances(
     profile: &str,
     region: &str,
-) -> Result<std::collections::HashMap<String, String>, String> {
-    let client = create_ssm_client(profile, region).await?;
-
-    let mut status_map = std::collections::HashMap::new();
-    let mut next_token: Option<String> = None;
+    ssm_only: bool,
+    filters: &InstanceFilters,
+) -> Result<Vec<Ec2Instance>, String> {
+    let filters = filters.to_ec2_filters();
 
-    loop {
-        let mut request = client.describe_instance_information();
+    let mut instances = describe_instances(profile, region, filters, vec![], ssm_only).await?;
 
-        if let Some(token) = next_token {
-            request = request.next_token(token);
+    // Sort by name (instances with names first, then by instance ID)
+    instances.sort_by(|a, b| {
+        match (&a.name, &b.name) {
+            (Some(name_a), Some(name_b)) => name_a.cmp(name_b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.instance_id.cmp(&b.instance_id),
         }
+    });
 
-        let result = request
-            .send()
-            .await
-            .map_err(|e| format!("Failed to get SSM status: {}", e))?;
-
-        for info in result.instance_information_list() {
-            if let (Some(id), Some(status)) = (info.instance_id(), info.ping_status()) {
-                status_map.insert(id.to_string(), status.as_str().to_string());
-            }
-        }
+    Ok(instances)
+}
 
-        next_token = result.next_token().map(|s| s.to_string());
-        if next_token.is_none() {
-            break;
-        }
+/// Get specific EC2 instances (any state) with SSM status. IDs that no longer exist
+/// (e.g. terminated a moment ago) are skipped instead of failing the whole call.
+pub async fn get_instances_by_id(
+    profile: &str,
+    region: &str,
+    instance_ids: &[String],
+) -> Result<Vec<Ec2Instance>, String> {
+    let mut instances = Vec::new();
+    for chunk in instance_ids.chunks(EC2_FILTER_VALUES_LIMIT) {
+        instances.extend(describe_instances(profile, region, vec![], chunk.to_vec(), false).await?);
     }
 
-    Ok(status_map)
+    Ok(instances)
 }
 
-/// List all EC2 instances with SSM status
-pub async fn list_instances(
+/// Describe EC2 instances matching filters/IDs and join them with SSM status. IDs go
+/// through an `instance-id` filter, which ignores unknown IDs where `InstanceIds` fails.
+async fn describe_instances(
     profile: &str,
     region: &str,
+    filters: Vec<aws_sdk_ec2::types::Filter>,
+    instance_ids: Vec<String>,
     ssm_only: bool,
 ) -> Result<Vec<Ec2Instance>, String> {
     let ec2_client = create_ec2_client(profile, region).await?;
 
-    // Get SSM-managed instances and their status
-    let ssm_instances = get_ssm_managed_instances(profile, region).await?;
-    let ssm_status = get_ssm_ping_status(profile, region).await?;
+    let mut filters = filters;
+    if !instance_ids.is_empty() {
+        filters.push(
+            aws_sdk_ec2::types::Filter::builder()
+                .name("instance-id")
+                .set_values(Some(instance_ids.clone()))
+                .build(),
+        );
+    }
+
+    // Get SSM-managed instances and their agent details, only scanning the whole
+    // account for filtered listings
+    let ssm_instances = if instance_ids.is_empty() {
+        get_ssm_instance_info(profile, region, &[]).await?
+    } else {
+        let mut found = HashMap::new();
+        for chunk in instance_ids.chunks(SSM_INSTANCE_IDS_LIMIT) {
+            found.extend(get_ssm_instance_info(profile, region, chunk).await?);
+        }
+        found
+    };
 
     // List EC2 instances
     let mut instances = Vec::new();
     let mut next_token: Option<String> = None;
 
     loop {
-        let mut request = ec2_client.describe_instances();
-
-        // Only get running instances
-        request = request.filters(
-            aws_sdk_ec2::types::Filter::builder()
-                .name("instance-state-name")
-                .values("running")
-                .build(),
-        );
+        let mut request = ec2_client
+            .describe_instances()
+            .set_filters((!filters.is_empty()).then(|| filters.clone()));
 
         if let Some(token) = next_token {
             request = request.next_token(token);
@@ -151,42 +315,15 @@ pub async fn list_instances(
 
         for reservation in result.reservations() {
             for instance in reservation.instances() {
-                let instance_id = instance.instance_id().unwrap_or_default().to_string();
-                let ssm_enabled = ssm_instances.contains(&instance_id);
+                let instance_id = instance.instance_id().unwrap_or_default();
+                let ssm_info = ssm_instances.get(instance_id).cloned();
 
                 // Skip non-SSM instances if ssm_only is true
-                if ssm_only && !ssm_enabled {
+                if ssm_only && ssm_info.is_none() {
                     continue;
                 }
 
-                // Get instance name from tags
-                let name = instance
-                    .tags()
-                    .iter()
-                    .find(|t| t.key() == Some("Name"))
-                    .and_then(|t| t.value())
-                    .map(|s| s.to_string());
-
-                instances.push(Ec2Instance {
-                    instance_id: instance_id.clone(),
-                    name,
-                    instance_type: instance
-                        .instance_type()
-                        .map(|t| t.as_str().to_string())
-                        .unwrap_or_default(),
-                    state: instance
-                        .state()
-                        .and_then(|s| s.name())
-                        .map(|n| n.as_str().to_string())
-                        .unwrap_or_default(),
-                    private_ip: instance.private_ip_address().map(|s| s.to_string()),
-                    public_ip: instance.public_ip_address().map(|s| s.to_string()),
-                    platform: instance
-                        .platform_details()
-                        .map(|s| s.to_string()),
-                    ssm_enabled,
-                    ssm_ping_status: ssm_status.get(&instance_id).cloned(),
-                });
+                instances.push(to_ec2_instance(instance, ssm_info));
             }
         }
 
@@ -196,23 +333,734 @@ pub async fn list_instances(
         }
     }
 
-    // Sort by name (instances with names first, then by instance ID)
-    instances.sort_by(|a, b| {
-        match (&a.name, &b.name) {
-            (Some(name_a), Some(name_b)) => name_a.cmp(name_b),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.instance_id.cmp(&b.instance_id),
-        }
-    });
-
     Ok(instances)
 }
 
+/// Convert an SDK instance and its SSM agent details into our instance model
+fn to_ec2_instance(
+    instance: &aws_sdk_ec2::types::Instance,
+    ssm_info: Option<SsmInstanceInfo>,
+) -> Ec2Instance {
+    let tags: HashMap<String, String> = instance
+        .tags()
+        .iter()
+        .filter_map(|t| Some((t.key()?.to_string(), t.value()?.to_string())))
+        .collect();
+    let name = tags.get("Name").cloned();
+
+    Ec2Instance {
+        instance_id: instance.instance_id().unwrap_or_default().to_string(),
+        name,
+        instance_type: instance
+            .instance_type()
+            .map(|t| t.as_str().to_string())
+            .unwrap_or_default(),
+        state: instance
+            .state()
+            .and_then(|s| s.name())
+            .map(|n| n.as_str().to_string())
+            .unwrap_or_default(),
+        private_ip: instance.private_ip_address().map(|s| s.to_string()),
+        public_ip: instance.public_ip_address().map(|s| s.to_string()),
+        platform: instance.platform_details().map(|s| s.to_string()),
+        hibernation_configured: instance
+            .hibernation_options()
+            .and_then(|h| h.configured())
+            .unwrap_or(false),
+        ssm_enabled: ssm_info.is_some(),
+        ssm_ping_status: ssm_info.as_ref().and_then(|i| i.ping_status.clone()),
+        ssm_agent_version: ssm_info.as_ref().and_then(|i| i.agent_version.clone()),
+        ssm_agent_outdated: ssm_info
+            .as_ref()
+            .is_some_and(|i| i.is_latest_version == Some(false)),
+        ssm_platform_name: ssm_info.as_ref().and_then(|i| i.platform_name.clone()),
+        ssm_platform_version: ssm_info.as_ref().and_then(|i| i.platform_version.clone()),
+        ssm_last_ping_at: ssm_info.as_ref().and_then(|i| i.last_ping_at),
+        ssm_association_status: ssm_info.as_ref().and_then(|i| i.association_status.clone()),
+        ssm_ip_address: ssm_info.and_then(|i| i.ip_address),
+        vpc_id: instance.vpc_id().map(|s| s.to_string()),
+        subnet_id: instance.subnet_id().map(|s| s.to_string()),
+        tags,
+    }
+}
+
 /// List only SSM-enabled instances
 pub async fn list_ssm_instances(
     profile: &str,
     region: &str,
 ) -> Result<Vec<Ec2Instance>, String> {
-    list_instances(profile, region, true).await
+    list_instances(profile, region, true, &InstanceFilters::default()).await
+}
+
+/// Group instances by the values of `tag_keys`, one level per key.
+/// Groups are sorted by value, with untagged instances last.
+pub fn group_instances(instances: Vec<Ec2Instance>, tag_keys: &[String]) -> Vec<InstanceGroup> {
+    let Some((tag_key, rest)) = tag_keys.split_first() else {
+        return vec![];
+    };
+
+    let mut by_value: BTreeMap<Option<String>, Vec<Ec2Instance>> = BTreeMap::new();
+    for instance in instances {
+        let value = instance.tags.get(tag_key).cloned();
+        by_value.entry(value).or_default().push(instance);
+    }
+
+    // None sorts first in a BTreeMap, move the untagged group to the end
+    let mut entries: Vec<_> = by_value.into_iter().collect();
+    let untagged = entries.iter().take_while(|(v, _)| v.is_none()).count();
+    entries.rotate_left(untagged);
+
+    entries
+        .into_iter()
+        .map(|(value, instances)| {
+            let instance_count = instances.len();
+            let (groups, instances) = if rest.is_empty() {
+                (vec![], instances)
+            } else {
+                (group_instances(instances, rest), vec![])
+            };
+
+            InstanceGroup {
+                tag_key: tag_key.clone(),
+                value,
+                instance_count,
+                groups,
+                instances,
+            }
+        })
+        .collect()
+}
+
+/// Power action on an EC2 instance
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceAction {
+    Start,
+    Stop,
+    Reboot,
+    /// Stop with hibernation, the instance must have been launched with it enabled
+    Hibernate,
+}
+
+impl InstanceAction {
+    /// State the instance ends up in once the action is done
+    pub fn target_state(&self) -> &'static str {
+        match self {
+            Self::Start | Self::Reboot => "running",
+            Self::Stop | Self::Hibernate => "stopped",
+        }
+    }
+}
+
+/// Start, stop, reboot or hibernate an instance, returning the state it moved to
+pub async fn change_instance_state(
+    profile: &str,
+    region: &str,
+    instance_id: &str,
+    action: InstanceAction,
+) -> Result<Option<String>, String> {
+    let client = create_ec2_client(profile, region).await?;
+
+    let state_name = |changes: &[aws_sdk_ec2::types::InstanceStateChange]| {
+        changes
+            .first()
+            .and_then(|c| c.current_state())
+            .and_then(|s| s.name())
+            .map(|n| n.as_str().to_string())
+    };
+
+    match action {
+        InstanceAction::Start => {
+            let result = client
+                .start_instances()
+                .instance_ids(instance_id)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to start instance: {}", e))?;
+            Ok(state_name(result.starting_instances()))
+        }
+        InstanceAction::Stop | InstanceAction::Hibernate => {
+            let result = client
+                .stop_instances()
+                .instance_ids(instance_id)
+                .hibernate(action == InstanceAction::Hibernate)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to stop instance: {}", e))?;
+            Ok(state_name(result.stopping_instances()))
+        }
+        InstanceAction::Reboot => {
+            client
+                .reboot_instances()
+                .instance_ids(instance_id)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to reboot instance: {}", e))?;
+            Ok(None)
+        }
+    }
+}
+
+/// Name fragments that mark an instance as a jump host
+const BASTION_HINTS: [&str; 3] = ["bastion", "jump", "ssm"];
+
+/// Pick SSM-enabled instances in `vpc_id` that can forward to a private endpoint.
+/// Online agents come first, then instances named like a bastion, then by name.
+pub fn rank_bastions(instances: Vec<Ec2Instance>, vpc_id: &str) -> Vec<Ec2Instance> {
+    let looks_like_bastion = |instance: &Ec2Instance| {
+        instance.name.as_deref().is_some_and(|name| {
+            let name = name.to_lowercase();
+            BASTION_HINTS.iter().any(|hint| name.contains(hint))
+        })
+    };
+
+    let mut candidates: Vec<Ec2Instance> = instances
+        .into_iter()
+        .filter(|i| i.ssm_enabled && i.state == "running" && i.vpc_id.as_deref() == Some(vpc_id))
+        .collect();
+
+    candidates.sort_by_key(|i| {
+        (
+            i.ssm_ping_status.as_deref() != Some("Online"),
+            !looks_like_bastion(i),
+            i.name.clone().unwrap_or_else(|| i.instance_id.clone()),
+        )
+    });
+
+    candidates
+}
+
+/// Suggest bastion instances for reaching an endpoint in `vpc_id`
+pub async fn suggest_bastions(
+    profile: &str,
+    region: &str,
+    vpc_id: &str,
+) -> Result<Vec<Ec2Instance>, String> {
+    let instances = list_ssm_instances(profile, region).await?;
+    Ok(rank_bastions(instances, vpc_id))
+}
+
+/// Everything about an instance that matters when debugging connectivity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceDetail {
+    pub instance: Ec2Instance,
+    pub vpc_cidr: Option<String>,
+    pub subnet_cidr: Option<String>,
+    pub availability_zone: Option<String>,
+    pub private_dns_name: Option<String>,
+    pub public_dns_name: Option<String>,
+    pub security_groups: Vec<SecurityGroupDetail>,
+    pub volumes: Vec<InstanceVolume>,
+    /// ARN of the IAM instance profile
+    pub iam_instance_profile: Option<String>,
+    pub launch_time: Option<i64>,
+    pub image_id: Option<String>,
+    /// Not available once the AMI is deregistered or no longer shared
+    pub image_name: Option<String>,
+    pub key_name: Option<String>,
+    pub architecture: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityGroupDetail {
+    pub group_id: String,
+    pub group_name: Option<String>,
+    pub description: Option<String>,
+    pub inbound_rules: Vec<SecurityGroupRule>,
+}
+
+/// One inbound rule source. AWS rules with several sources are split up.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SecurityGroupRule {
+    /// "tcp", "udp", "icmp", ... or "all"
+    pub protocol: String,
+    /// Not set when the rule covers all ports
+    pub from_port: Option<i32>,
+    pub to_port: Option<i32>,
+    /// IPv4/IPv6 CIDR, security group ID or prefix list ID
+    pub source: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceVolume {
+    pub volume_id: String,
+    pub device_name: Option<String>,
+    pub size_gib: Option<i32>,
+    pub volume_type: Option<String>,
+    pub iops: Option<i32>,
+    pub encrypted: bool,
+    pub delete_on_termination: bool,
+    /// in-use, available, error, ...
+    pub state: Option<String>,
+}
+
+/// Flatten a security group permission into one rule per source
+pub fn permission_rules(permission: &aws_sdk_ec2::types::IpPermission) -> Vec<SecurityGroupRule> {
+    let protocol = match permission.ip_protocol() {
+        Some("-1") | None => "all".to_string(),
+        Some(p) => p.to_string(),
+    };
+    // -1 ports mean "all" for ICMP and all-protocol rules
+    let (from_port, to_port) = if protocol == "all" {
+        (None, None)
+    } else {
+        (
+            permission.from_port().filter(|p| *p >= 0),
+            permission.to_port().filter(|p| *p >= 0),
+        )
+    };
+
+    let sources = permission
+        .ip_ranges()
+        .iter()
+        .map(|r| (r.cidr_ip(), r.description()))
+        .chain(
+            permission
+                .ipv6_ranges()
+                .iter()
+                .map(|r| (r.cidr_ipv6(), r.description())),
+        )
+        .chain(
+            permission
+                .user_id_group_pairs()
+                .iter()
+                .map(|g| (g.group_id(), g.description())),
+        )
+        .chain(
+            permission
+                .prefix_list_ids()
+                .iter()
+                .map(|p| (p.prefix_list_id(), p.description())),
+        );
+
+    sources
+        .filter_map(|(source, description)| {
+            Some(SecurityGroupRule {
+                protocol: protocol.clone(),
+                from_port,
+                to_port,
+                source: source?.to_string(),
+                description: description.map(|s| s.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Describe security groups with their inbound rules
+async fn describe_security_group_details(
+    client: &Ec2Client,
+    group_ids: Vec<String>,
+) -> Result<Vec<SecurityGroupDetail>, String> {
+    if group_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let result = client
+        .describe_security_groups()
+        .set_group_ids(Some(group_ids))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe security groups: {}", e))?;
+
+    Ok(result
+        .security_groups()
+        .iter()
+        .map(|g| SecurityGroupDetail {
+            group_id: g.group_id().unwrap_or_default().to_string(),
+            group_name: g.group_name().map(|s| s.to_string()),
+            description: g.description().map(|s| s.to_string()),
+            inbound_rules: g
+                .ip_permissions()
+                .iter()
+                .flat_map(permission_rules)
+                .collect(),
+        })
+        .collect())
+}
+
+/// Describe the EBS volumes attached to an instance, given `(device name,
+/// delete on termination)` per volume ID
+async fn describe_instance_volumes(
+    client: &Ec2Client,
+    attachments: &HashMap<String, (Option<String>, bool)>,
+) -> Result<Vec<InstanceVolume>, String> {
+    if attachments.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let result = client
+        .describe_volumes()
+        .set_volume_ids(Some(attachments.keys().cloned().collect()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe volumes: {}", e))?;
+
+    let mut volumes: Vec<InstanceVolume> = result
+        .volumes()
+        .iter()
+        .map(|volume| {
+            let volume_id = volume.volume_id().unwrap_or_default().to_string();
+            let (device_name, delete_on_termination) =
+                attachments.get(&volume_id).cloned().unwrap_or_default();
+
+            InstanceVolume {
+                volume_id,
+                device_name,
+                size_gib: volume.size(),
+                volume_type: volume.volume_type().map(|t| t.as_str().to_string()),
+                iops: volume.iops(),
+                encrypted: volume.encrypted().unwrap_or(false),
+                delete_on_termination,
+                state: volume.state().map(|s| s.as_str().to_string()),
+            }
+        })
+        .collect();
+    volumes.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+
+    Ok(volumes)
+}
+
+/// CIDR block of a subnet, logged and left out on failure
+async fn lookup_subnet_cidr(client: &Ec2Client, subnet_id: Option<&str>) -> Option<String> {
+    let subnet_id = subnet_id?;
+    match client.describe_subnets().subnet_ids(subnet_id).send().await {
+        Ok(result) => result
+            .subnets()
+            .first()
+            .and_then(|s| s.cidr_block())
+            .map(|s| s.to_string()),
+        Err(e) => {
+            tracing::warn!("Failed to describe subnet {}: {}", subnet_id, e);
+            None
+        }
+    }
+}
+
+/// CIDR block of a VPC, logged and left out on failure
+async fn lookup_vpc_cidr(client: &Ec2Client, vpc_id: Option<&str>) -> Option<String> {
+    let vpc_id = vpc_id?;
+    match client.describe_vpcs().vpc_ids(vpc_id).send().await {
+        Ok(result) => result
+            .vpcs()
+            .first()
+            .and_then(|v| v.cidr_block())
+            .map(|s| s.to_string()),
+        Err(e) => {
+            tracing::warn!("Failed to describe VPC {}: {}", vpc_id, e);
+            None
+        }
+    }
+}
+
+/// Name of an AMI, logged and left out on failure (e.g. deregistered images)
+async fn lookup_image_name(client: &Ec2Client, image_id: Option<&str>) -> Option<String> {
+    let image_id = image_id?;
+    match client.describe_images().image_ids(image_id).send().await {
+        Ok(result) => result
+            .images()
+            .first()
+            .and_then(|i| i.name())
+            .map(|s| s.to_string()),
+        Err(e) => {
+            tracing::warn!("Failed to describe AMI {}: {}", image_id, e);
+            None
+        }
+    }
+}
+
+/// Describe an instance with its network, security groups, volumes, IAM profile and AMI
+pub async fn describe_instance_detail(
+    profile: &str,
+    region: &str,
+    instance_id: &str,
+) -> Result<InstanceDetail, String> {
+    let client = create_ec2_client(profile, region).await?;
+    let instance_ids = [instance_id.to_string()];
+
+    let (result, ssm_instances) = tokio::join!(
+        client.describe_instances().instance_ids(instance_id).send(),
+        get_ssm_instance_info(profile, region, &instance_ids)
+    );
+    let result = result.map_err(|e| format!("Failed to describe instance: {}", e))?;
+
+    let raw = result
+        .reservations()
+        .iter()
+        .flat_map(|r| r.instances())
+        .next()
+        .ok_or_else(|| format!("Instance not found: {}", instance_id))?;
+
+    // The instance is still worth showing without its SSM agent details
+    let ssm_info = match ssm_instances {
+        Ok(mut instances) => instances.remove(instance_id),
+        Err(e) => {
+            tracing::warn!("Failed to get SSM details of {}: {}", instance_id, e);
+            None
+        }
+    };
+    let instance = to_ec2_instance(raw, ssm_info);
+
+    let group_ids: Vec<String> = raw
+        .security_groups()
+        .iter()
+        .filter_map(|g| g.group_id())
+        .map(|s| s.to_string())
+        .collect();
+
+    // Attached EBS volumes, keyed by volume ID
+    let attachments: HashMap<String, (Option<String>, bool)> = raw
+        .block_device_mappings()
+        .iter()
+        .filter_map(|m| {
+            let ebs = m.ebs()?;
+            Some((
+                ebs.volume_id()?.to_string(),
+                (
+                    m.device_name().map(|s| s.to_string()),
+                    ebs.delete_on_termination().unwrap_or(false),
+                ),
+            ))
+        })
+        .collect();
+
+    let (security_groups, volumes, subnet_cidr, vpc_cidr, image_name) = tokio::join!(
+        describe_security_group_details(&client, group_ids),
+        describe_instance_volumes(&client, &attachments),
+        lookup_subnet_cidr(&client, raw.subnet_id()),
+        lookup_vpc_cidr(&client, raw.vpc_id()),
+        lookup_image_name(&client, raw.image_id()),
+    );
+
+    Ok(InstanceDetail {
+        vpc_cidr,
+        subnet_cidr,
+        availability_zone: raw
+            .placement()
+            .and_then(|p| p.availability_zone())
+            .map(|s| s.to_string()),
+        private_dns_name: raw
+            .private_dns_name()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+        public_dns_name: raw
+            .public_dns_name()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+        security_groups: security_groups?,
+        volumes: volumes?,
+        iam_instance_profile: raw
+            .iam_instance_profile()
+            .and_then(|p| p.arn())
+            .map(|s| s.to_string()),
+        launch_time: raw.launch_time().map(|d| (d.as_secs_f64() * 1000.0) as i64),
+        image_id: raw.image_id().map(|s| s.to_string()),
+        image_name,
+        key_name: raw.key_name().map(|s| s.to_string()),
+        architecture: raw.architecture().map(|a| a.as_str().to_string()),
+        instance,
+    })
+}
+
+/// Serial console output of an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsoleOutput {
+    pub instance_id: String,
+    pub output: String,
+    /// When the output was last updated
+    pub timestamp: Option<i64>,
+    /// Whether this is the latest output, only Nitro instances support it
+    pub latest: bool,
+}
+
+/// Decode base64 console output. Consoles print binary junk and CRLF line endings.
+pub fn decode_console_output(encoded: &str) -> Result<String, String> {
+    let bytes = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| format!("Failed to decode console output: {}", e))?;
+    Ok(String::from_utf8_lossy(&bytes).replace("\r\n", "\n"))
+}
+
+/// Get the serial console output of an instance. The latest output is requested,
+/// falling back to the last buffered output on instances that don't support it.
+pub async fn get_console_output(
+    profile: &str,
+    region: &str,
+    instance_id: &str,
+) -> Result<ConsoleOutput, String> {
+    let client = create_ec2_client(profile, region).await?;
+
+    let (result, latest) = match client
+        .get_console_output()
+        .instance_id(instance_id)
+        .latest(true)
+        .send()
+        .await
+    {
+        Ok(result) => (result, true),
+        Err(e) => {
+            tracing::debug!("Latest console output unavailable: {}", e);
+            let result = client
+                .get_console_output()
+                .instance_id(instance_id)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to get console output: {}", e))?;
+            (result, false)
+        }
+    };
+
+    let output = match result.output() {
+        Some(encoded) => decode_console_output(encoded)?,
+        None => String::new(),
+    };
+
+    Ok(ConsoleOutput {
+        instance_id: instance_id.to_string(),
+        output,
+        timestamp: result
+            .timestamp()
+            .map(|d| (d.as_secs_f64() * 1000.0) as i64),
+        latest,
+    })
+}
+
+/// Take a screenshot of the instance's console, returned as a base64 JPEG
+pub async fn get_console_screenshot(
+    profile: &str,
+    region: &str,
+    instance_id: &str,
+) -> Result<String, String> {
+    let client = create_ec2_client(profile, region).await?;
+
+    let result = client
+        .get_console_screenshot()
+        .instance_id(instance_id)
+        .wake_up(true)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get console screenshot: {}", e))?;
+
+    result
+        .image_data()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "No screenshot returned".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(id: &str, name: Option<&str>, vpc: &str, ping: &str, ssm: bool) -> Ec2Instance {
+        Ec2Instance {
+            instance_id: id.to_string(),
+            name: name.map(|s| s.to_string()),
+            state: "running".to_string(),
+            ssm_enabled: ssm,
+            ssm_ping_status: Some(ping.to_string()),
+            vpc_id: Some(vpc.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn tagged(id: &str, tags: &[(&str, &str)]) -> Ec2Instance {
+        let mut instance = instance(id, None, "vpc-a", "Online", true);
+        instance.tags = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        instance
+    }
+
+    #[test]
+    fn test_group_instances() {
+        const ASG: &str = "aws:autoscaling:groupName";
+        let instances = vec![
+            tagged("i-1", &[("Environment", "prod"), (ASG, "api")]),
+            tagged("i-2", &[("Environment", "prod"), (ASG, "api")]),
+            tagged("i-3", &[("Environment", "prod")]),
+            tagged("i-4", &[("Environment", "dev"), (ASG, "api")]),
+            tagged("i-5", &[]),
+        ];
+        let keys = vec!["Environment".to_string(), ASG.to_string()];
+
+        let groups = group_instances(instances, &keys);
+
+        let values: Vec<Option<&str>> = groups.iter().map(|g| g.value.as_deref()).collect();
+        assert_eq!(values, vec![Some("dev"), Some("prod"), None]);
+
+        let prod = &groups[1];
+        assert_eq!(prod.instance_count, 3);
+        assert!(prod.instances.is_empty());
+        assert_eq!(prod.groups.len(), 2);
+        assert_eq!(prod.groups[0].value.as_deref(), Some("api"));
+        assert_eq!(prod.groups[0].instance_count, 2);
+        assert_eq!(prod.groups[1].value, None);
+        assert_eq!(prod.groups[1].instances[0].instance_id, "i-3");
+
+        assert!(group_instances(vec![tagged("i-1", &[])], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_decode_console_output() {
+        let encoded = BASE64.encode(b"[    0.000000] Linux version 6.1\r\nKernel panic\xff\r\n");
+        assert_eq!(
+            decode_console_output(&encoded).unwrap(),
+            "[    0.000000] Linux version 6.1\nKernel panic\u{fffd}\n"
+        );
+        assert!(decode_console_output("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_permission_rules() {
+        use aws_sdk_ec2::types::{IpPermission, IpRange, UserIdGroupPair};
+
+        let permission = IpPermission::builder()
+            .ip_protocol("tcp")
+            .from_port(22)
+            .to_port(22)
+            .ip_ranges(
+                IpRange::builder()
+                    .cidr_ip("10.0.0.0/16")
+                    .description("VPN")
+                    .build(),
+            )
+            .user_id_group_pairs(UserIdGroupPair::builder().group_id("sg-bastion").build())
+            .build();
+
+        let rules = permission_rules(&permission);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].source, "10.0.0.0/16");
+        assert_eq!(rules[0].description.as_deref(), Some("VPN"));
+        assert_eq!((rules[1].from_port, rules[1].to_port), (Some(22), Some(22)));
+        assert_eq!(rules[1].source, "sg-bastion");
+
+        let all = IpPermission::builder()
+            .ip_protocol("-1")
+            .from_port(-1)
+            .to_port(-1)
+            .ip_ranges(IpRange::builder().cidr_ip("0.0.0.0/0").build())
+            .build();
+        let rules = permission_rules(&all);
+        assert_eq!(rules[0].protocol, "all");
+        assert_eq!(rules[0].from_port, None);
+    }
+
+    #[test]
+    fn test_rank_bastions() {
+        let instances = vec![
+            instance("i-1", Some("api-server"), "vpc-a", "Online", true),
+            instance("i-2", Some("prod-bastion"), "vpc-a", "Online", true),
+            instance("i-3", Some("jumpbox"), "vpc-a", "ConnectionLost", true),
+            instance("i-4", Some("bastion"), "vpc-b", "Online", true),
+            instance("i-5", Some("worker"), "vpc-a", "Online", false),
+        ];
+
+        let ranked: Vec<String> = rank_bastions(instances, "vpc-a")
+            .into_iter()
+            .map(|i| i.instance_id)
+            .collect();
+
+        assert_eq!(ranked, vec!["i-2", "i-1", "i-3"]);
+    }
 }
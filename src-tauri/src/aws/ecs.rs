@@ -1,7 +1,13 @@
 use aws_config::BehaviorVersion;
+use aws_sdk_ecs::types::{
+    AssignPublicIp, AwsVpcConfiguration, ClusterField, ClusterSettingName, ContainerOverride,
+    KeyValuePair, LaunchType, ManagedAgentName, NetworkConfiguration, TaskOverride,
+};
 use aws_sdk_ecs::Client as EcsClient;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use super::ec2::{get_instances_by_id, Ec2Instance};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EcsCluster {
@@ -10,6 +16,8 @@ pub struct EcsCluster {
     pub status: String,
     pub running_tasks_count: i32,
     pub services_count: i32,
+    /// Container Insights setting (enabled, enhanced or disabled)
+    pub container_insights: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +29,70 @@ pub struct EcsService {
     pub desired_count: i32,
     pub running_count: i32,
     pub launch_type: Option<String>,
+    pub enable_execute_command: bool,
+    /// Rollout state of the primary deployment (IN_PROGRESS, COMPLETED, FAILED)
+    pub rollout_state: Option<String>,
+    pub load_balancers: Vec<EcsLoadBalancer>,
+    /// Cloud Map service registries
+    pub service_registries: Vec<EcsServiceRegistry>,
+    /// Service Connect configuration of the primary deployment
+    pub service_connect: Option<EcsServiceConnect>,
+}
+
+/// Load balancer target group a service registers its tasks with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsLoadBalancer {
+    pub target_group_arn: Option<String>,
+    /// Only set for Classic Load Balancers
+    pub load_balancer_name: Option<String>,
+    pub container_name: Option<String>,
+    pub container_port: Option<i32>,
+}
+
+/// Cloud Map registry a service registers its tasks with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsServiceRegistry {
+    pub registry_arn: Option<String>,
+    /// Port for SRV records when using awsvpc networking
+    pub port: Option<i32>,
+    pub container_name: Option<String>,
+    pub container_port: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsServiceConnect {
+    pub enabled: bool,
+    /// Cloud Map namespace
+    pub namespace: Option<String>,
+    pub services: Vec<EcsServiceConnectService>,
+}
+
+/// Port exposed through Service Connect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsServiceConnectService {
+    /// Name of the port mapping in the task definition
+    pub port_name: String,
+    pub discovery_name: Option<String>,
+    pub ingress_port_override: Option<i32>,
+    pub client_aliases: Vec<EcsServiceConnectAlias>,
+}
+
+/// DNS name and port clients in the namespace use to reach a Service Connect service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsServiceConnectAlias {
+    pub port: i32,
+    pub dns_name: Option<String>,
+}
+
+/// Address of a running task, for pre-filling a port forward to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEndpoint {
+    pub task_arn: String,
+    /// ENI address in awsvpc mode, otherwise the private IP of the container instance
+    pub private_ip: Option<String>,
+    /// Port reachable at `private_ip` (the host port in bridge/host network mode)
+    pub port: Option<i32>,
+    pub container_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +105,12 @@ pub struct EcsTask {
     pub launch_type: Option<String>,
     pub containers: Vec<EcsContainer>,
     pub enable_execute_command: bool,
+    pub stopped_reason: Option<String>,
+    /// Task group, `service:<name>` for service tasks and `family:<name>` by default otherwise
+    pub group: Option<String>,
+    /// Who started the task, e.g. `events-rule/nightly` for scheduled tasks
+    pub started_by: Option<String>,
+    pub started_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +119,151 @@ pub struct EcsContainer {
     pub runtime_id: Option<String>,
     pub last_status: String,
     pub health_status: Option<String>,
+    pub exit_code: Option<i32>,
+    pub reason: Option<String>,
+    /// Status of the ExecuteCommandAgent managed agent
+    pub exec_agent_status: Option<String>,
+}
+
+/// awsvpc network configuration used when launching a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsNetworkConfig {
+    pub subnets: Vec<String>,
+    pub security_groups: Vec<String>,
+    pub assign_public_ip: bool,
+}
+
+/// Per-container command/environment override for a one-off task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerOverrideInput {
+    pub name: String,
+    pub command: Option<Vec<String>>,
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+}
+
+/// Input for running a one-off task from an existing task definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunTaskInput {
+    pub cluster: String,
+    pub task_definition: String,
+    #[serde(default)]
+    pub container_overrides: Vec<ContainerOverrideInput>,
+    /// Service whose network configuration and launch type are copied
+    pub source_service: Option<String>,
+    /// Explicit network configuration, takes precedence over the source service
+    pub network_config: Option<EcsNetworkConfig>,
+    pub launch_type: Option<String>,
+    #[serde(default)]
+    pub enable_execute_command: bool,
+}
+
+/// CloudWatch Logs location of a container using the awslogs driver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerLogTarget {
+    pub container: String,
+    pub log_group: String,
+    pub log_stream: String,
+    pub region: Option<String>,
+}
+
+/// Task/container details relevant to ECS Exec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskExecDetails {
+    pub task_arn: String,
+    pub last_status: String,
+    pub launch_type: Option<String>,
+    pub platform_version: Option<String>,
+    pub enable_execute_command: bool,
+    pub task_role_arn: Option<String>,
+    pub container_status: Option<String>,
+    pub exec_agent_status: Option<String>,
+    pub exec_agent_reason: Option<String>,
+}
+
+/// Cluster-level ECS Exec configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClusterExecConfig {
+    pub kms_key_id: Option<String>,
+    pub logging: Option<String>,
+    pub cloudwatch_log_group: Option<String>,
+    pub s3_bucket: Option<String>,
+}
+
+/// An entry from a service's event log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EcsServiceEvent {
+    pub id: String,
+    pub created_at: Option<i64>,
+    pub message: String,
+}
+
+/// A deployment of a service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EcsDeployment {
+    pub id: String,
+    pub status: String,
+    pub task_definition: Option<String>,
+    pub rollout_state: Option<String>,
+    pub rollout_state_reason: Option<String>,
+    pub desired_count: i32,
+    pub running_count: i32,
+    pub pending_count: i32,
+    pub failed_tasks: i32,
+    pub created_at: Option<i64>,
+    pub updated_at: Option<i64>,
+}
+
+/// Service state with its recent events and deployments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsServiceSnapshot {
+    pub service: EcsService,
+    pub pending_count: i32,
+    /// Most recent first, as returned by ECS
+    pub events: Vec<EcsServiceEvent>,
+    pub deployments: Vec<EcsDeployment>,
+}
+
+/// EC2 container instance registered to a cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsContainerInstance {
+    pub arn: String,
+    pub ec2_instance_id: Option<String>,
+    pub status: String,
+    pub agent_connected: bool,
+    pub agent_version: Option<String>,
+    pub docker_version: Option<String>,
+    pub capacity_provider_name: Option<String>,
+    pub registered_cpu: i32,
+    pub remaining_cpu: i32,
+    pub registered_memory: i32,
+    pub remaining_memory: i32,
+    pub running_tasks_count: i32,
+    pub pending_tasks_count: i32,
+    /// Matching EC2 instance (for opening an SSM session on the host)
+    pub ec2_instance: Option<Ec2Instance>,
+}
+
+/// Capacity provider associated with a cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsCapacityProvider {
+    pub name: String,
+    pub arn: Option<String>,
+    pub status: Option<String>,
+    pub auto_scaling_group_arn: Option<String>,
+    pub managed_scaling_status: Option<String>,
+    pub target_capacity: Option<i32>,
+    pub managed_termination_protection: Option<String>,
+    /// Weight/base in the cluster's default strategy, if it is part of it
+    pub default_weight: Option<i32>,
+    pub default_base: Option<i32>,
+}
+
+/// Standalone tasks sharing a group (or started-by when there is no group)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsTaskGroup {
+    pub name: String,
+    pub tasks: Vec<EcsTask>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +271,8 @@ pub struct EcsResources {
     pub clusters: Vec<EcsCluster>,
     pub services: HashMap<String, Vec<EcsService>>,
     pub tasks: HashMap<String, Vec<EcsTask>>,
+    /// Running tasks that belong to no service, keyed by cluster ARN
+    pub standalone_tasks: HashMap<String, Vec<EcsTaskGroup>>,
 }
 
 /// Create an ECS client with the specified profile and region
@@ -82,6 +307,7 @@ pub async fn list_clusters(profile: &str, region: &str) -> Result<Vec<EcsCluster
     let describe_result = client
         .describe_clusters()
         .set_clusters(Some(cluster_arns.to_vec()))
+        .include(ClusterField::Settings)
         .send()
         .await
         .map_err(|e| format!("Failed to describe clusters: {}", e))?;
@@ -89,13 +315,7 @@ pub async fn list_clusters(profile: &str, region: &str) -> Result<Vec<EcsCluster
     let clusters = describe_result
         .clusters()
         .iter()
-        .map(|c| EcsCluster {
-            arn: c.cluster_arn().unwrap_or_default().to_string(),
-            name: c.cluster_name().unwrap_or_default().to_string(),
-            status: c.status().unwrap_or_default().to_string(),
-            running_tasks_count: c.running_tasks_count(),
-            services_count: c.active_services_count(),
-        })
+        .map(to_ecs_cluster)
         .collect();
 
     Ok(clusters)
@@ -149,17 +369,7 @@ pub async fn list_services(
             .await
             .map_err(|e| format!("Failed to describe services: {}", e))?;
 
-        for s in describe_result.services() {
-            services.push(EcsService {
-                arn: s.service_arn().unwrap_or_default().to_string(),
-                name: s.service_name().unwrap_or_default().to_string(),
-                cluster_arn: s.cluster_arn().unwrap_or_default().to_string(),
-                status: s.status().unwrap_or_default().to_string(),
-                desired_count: s.desired_count(),
-                running_count: s.running_count(),
-                launch_type: s.launch_type().map(|lt| lt.as_str().to_string()),
-            });
-        }
+        services.extend(describe_result.services().iter().map(to_ecs_service));
     }
 
     Ok(services)
@@ -204,35 +414,1049 @@ pub async fn list_tasks(
         .await
         .map_err(|e| format!("Failed to describe tasks: {}", e))?;
 
-    let tasks = describe_result
+    let tasks = describe_result.tasks().iter().map(to_ecs_task).collect();
+
+    Ok(tasks)
+}
+
+/// List and describe every running task in a cluster, service tasks included
+async fn list_cluster_tasks(
+    profile: &str,
+    region: &str,
+    cluster_arn: &str,
+) -> Result<Vec<EcsTask>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let mut task_arns = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .list_tasks()
+            .cluster(cluster_arn)
+            .desired_status(aws_sdk_ecs::types::DesiredStatus::Running);
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list tasks: {}", e))?;
+
+        task_arns.extend(result.task_arns().to_vec());
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    // Describe tasks (max 100 at a time)
+    let mut tasks = Vec::new();
+
+    for chunk in task_arns.chunks(100) {
+        let result = client
+            .describe_tasks()
+            .cluster(cluster_arn)
+            .set_tasks(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to describe tasks: {}", e))?;
+
+        tasks.extend(result.tasks().iter().map(to_ecs_task));
+    }
+
+    Ok(tasks)
+}
+
+/// Split a cluster's tasks into tasks per service name and tasks that belong to no
+/// service (scheduled jobs, RunTask, batch). Service tasks always have a
+/// `service:<name>` group.
+fn split_cluster_tasks(tasks: Vec<EcsTask>) -> (HashMap<String, Vec<EcsTask>>, Vec<EcsTask>) {
+    let mut by_service: HashMap<String, Vec<EcsTask>> = HashMap::new();
+    let mut standalone = Vec::new();
+
+    for task in tasks {
+        let service = task
+            .group
+            .as_deref()
+            .and_then(|g| g.strip_prefix("service:"))
+            .map(|s| s.to_string());
+        match service {
+            Some(service) => by_service.entry(service).or_default().push(task),
+            None => standalone.push(task),
+        }
+    }
+
+    (by_service, standalone)
+}
+
+/// Group tasks by task group, falling back to started-by, sorted by name
+fn group_standalone_tasks(tasks: Vec<EcsTask>) -> Vec<EcsTaskGroup> {
+    let mut groups: BTreeMap<String, Vec<EcsTask>> = BTreeMap::new();
+
+    for task in tasks {
+        let name = task
+            .group
+            .clone()
+            .or_else(|| task.started_by.clone())
+            .unwrap_or_else(|| "ungrouped".to_string());
+        groups.entry(name).or_default().push(task);
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, tasks)| EcsTaskGroup { name, tasks })
+        .collect()
+}
+
+/// Extract the task ID from a task ARN
+pub fn task_id_from_arn(task_arn: &str) -> &str {
+    task_arn.rsplit('/').next().unwrap_or(task_arn)
+}
+
+/// Build the SSM target of an ECS container, `ecs:<cluster>_<taskId>_<runtimeId>`.
+/// Accepts cluster and task ARNs as well as names/IDs.
+pub fn ecs_ssm_target(cluster: &str, task: &str, runtime_id: &str) -> String {
+    let cluster_name = cluster.rsplit('/').next().unwrap_or(cluster);
+    format!(
+        "ecs:{}_{}_{}",
+        cluster_name,
+        task_id_from_arn(task),
+        runtime_id
+    )
+}
+
+/// Get the awsvpc network configuration of a service
+pub async fn get_service_network_config(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_name: &str,
+) -> Result<Option<EcsNetworkConfig>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_services()
+        .cluster(cluster)
+        .services(service_name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe service: {}", e))?;
+
+    let service = result
+        .services()
+        .first()
+        .ok_or_else(|| format!("Service not found: {}", service_name))?;
+
+    Ok(service
+        .network_configuration()
+        .and_then(|n| n.awsvpc_configuration())
+        .map(|vpc| EcsNetworkConfig {
+            subnets: vpc.subnets().to_vec(),
+            security_groups: vpc.security_groups().to_vec(),
+            assign_public_ip: vpc.assign_public_ip() == Some(&AssignPublicIp::Enabled),
+        }))
+}
+
+/// Build the SDK network configuration from our model
+fn build_network_configuration(config: &EcsNetworkConfig) -> Result<NetworkConfiguration, String> {
+    let assign_public_ip = if config.assign_public_ip {
+        AssignPublicIp::Enabled
+    } else {
+        AssignPublicIp::Disabled
+    };
+
+    let vpc = AwsVpcConfiguration::builder()
+        .set_subnets(Some(config.subnets.clone()))
+        .set_security_groups(Some(config.security_groups.clone()))
+        .assign_public_ip(assign_public_ip)
+        .build()
+        .map_err(|e| format!("Invalid network configuration: {}", e))?;
+
+    Ok(NetworkConfiguration::builder()
+        .awsvpc_configuration(vpc)
+        .build())
+}
+
+/// Start a one-off task from an existing task definition
+pub async fn run_task(
+    profile: &str,
+    region: &str,
+    input: &RunTaskInput,
+) -> Result<EcsTask, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let mut request = client
+        .run_task()
+        .cluster(&input.cluster)
+        .task_definition(&input.task_definition)
+        .started_by("aws-connector")
+        .enable_execute_command(input.enable_execute_command);
+
+    let mut network_config = input
+        .network_config
+        .as_ref()
+        .map(build_network_configuration)
+        .transpose()?;
+
+    // Copy launch settings from the source service unless explicitly overridden
+    if let Some(service_name) = &input.source_service {
+        let result = client
+            .describe_services()
+            .cluster(&input.cluster)
+            .services(service_name)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to describe service: {}", e))?;
+
+        let service = result
+            .services()
+            .first()
+            .ok_or_else(|| format!("Service not found: {}", service_name))?;
+
+        if network_config.is_none() {
+            network_config = service.network_configuration().cloned();
+        }
+
+        if input.launch_type.is_none() {
+            // A task can use either a launch type or a capacity provider strategy, not both
+            if service.capacity_provider_strategy().is_empty() {
+                request = request.set_launch_type(service.launch_type().cloned());
+            } else {
+                request = request.set_capacity_provider_strategy(Some(
+                    service.capacity_provider_strategy().to_vec(),
+                ));
+            }
+            request =
+                request.set_platform_version(service.platform_version().map(|s| s.to_string()));
+        }
+    }
+
+    if let Some(launch_type) = &input.launch_type {
+        request = request.launch_type(LaunchType::from(launch_type.as_str()));
+    }
+
+    request = request.set_network_configuration(network_config);
+
+    if !input.container_overrides.is_empty() {
+        let overrides = input
+            .container_overrides
+            .iter()
+            .map(|o| {
+                let environment: Vec<KeyValuePair> = o
+                    .environment
+                    .iter()
+                    .map(|(name, value)| KeyValuePair::builder().name(name).value(value).build())
+                    .collect();
+
+                ContainerOverride::builder()
+                    .name(&o.name)
+                    .set_command(o.command.clone())
+                    .set_environment((!environment.is_empty()).then_some(environment))
+                    .build()
+            })
+            .collect();
+
+        request = request.overrides(
+            TaskOverride::builder()
+                .set_container_overrides(Some(overrides))
+                .build(),
+        );
+    }
+
+    let result = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to run task: {}", e))?;
+
+    if let Some(failure) = result.failures().first() {
+        return Err(format!(
+            "Failed to run task: {}",
+            failure.reason().unwrap_or("unknown reason")
+        ));
+    }
+
+    result
         .tasks()
+        .first()
+        .map(to_ecs_task)
+        .ok_or_else(|| "Failed to run task: no task was started".to_string())
+}
+
+/// Describe a single task
+pub async fn describe_task(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    task_arn: &str,
+) -> Result<EcsTask, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_tasks()
+        .cluster(cluster)
+        .tasks(task_arn)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe task: {}", e))?;
+
+    result
+        .tasks()
+        .first()
+        .map(to_ecs_task)
+        .ok_or_else(|| format!("Task not found: {}", task_arn))
+}
+
+/// Resolve the private IP and port of a task. `container_port` is the port inside
+/// the container; in bridge/host network mode it is mapped to the host port.
+pub async fn resolve_task_endpoint(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    task_arn: &str,
+    container_name: Option<&str>,
+    container_port: Option<i32>,
+) -> Result<TaskEndpoint, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_tasks()
+        .cluster(cluster)
+        .tasks(task_arn)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe task: {}", e))?;
+
+    let task = result
+        .tasks()
+        .first()
+        .ok_or_else(|| format!("Task not found: {}", task_arn))?;
+
+    let container = match container_name {
+        Some(name) => task.containers().iter().find(|c| c.name() == Some(name)),
+        None => task.containers().first(),
+    };
+
+    // awsvpc tasks have their own ENI
+    let eni_ip = task
+        .attachments()
         .iter()
-        .map(|t| {
-            let containers = t
-                .containers()
+        .filter(|a| a.r#type() == Some("ElasticNetworkInterface"))
+        .flat_map(|a| a.details())
+        .find(|d| d.name() == Some("privateIPv4Address"))
+        .and_then(|d| d.value())
+        .or_else(|| {
+            container
+                .and_then(|c| c.network_interfaces().first())
+                .and_then(|ni| ni.private_ipv4_address())
+        })
+        .map(|s| s.to_string());
+
+    if eni_ip.is_some() {
+        return Ok(TaskEndpoint {
+            task_arn: task_arn.to_string(),
+            private_ip: eni_ip,
+            port: container_port,
+            container_name: container.and_then(|c| c.name()).map(|s| s.to_string()),
+        });
+    }
+
+    // bridge/host mode: reach the container through its container instance
+    let binding = container.and_then(|c| {
+        c.network_bindings()
+            .iter()
+            .find(|b| container_port.is_none() || b.container_port() == container_port)
+    });
+    let port = binding.and_then(|b| b.host_port()).or(container_port);
+
+    let mut private_ip = None;
+    if let Some(container_instance_arn) = task.container_instance_arn() {
+        let result = client
+            .describe_container_instances()
+            .cluster(cluster)
+            .container_instances(container_instance_arn)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to describe container instance: {}", e))?;
+
+        let instance_ids: Vec<String> = result
+            .container_instances()
+            .iter()
+            .filter_map(|ci| ci.ec2_instance_id().map(|s| s.to_string()))
+            .collect();
+
+        if !instance_ids.is_empty() {
+            private_ip = get_instances_by_id(profile, region, &instance_ids)
+                .await?
+                .into_iter()
+                .find_map(|i| i.private_ip);
+        }
+    }
+
+    Ok(TaskEndpoint {
+        task_arn: task_arn.to_string(),
+        private_ip,
+        port,
+        container_name: container.and_then(|c| c.name()).map(|s| s.to_string()),
+    })
+}
+
+/// Resolve the endpoint of a running task of a service, using the container and
+/// port of its load balancer or Cloud Map registry
+pub async fn resolve_service_endpoint(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_name: &str,
+) -> Result<TaskEndpoint, String> {
+    let service = describe_service(profile, region, cluster, service_name).await?;
+
+    let (container_name, container_port) = service
+        .load_balancers
+        .iter()
+        .find(|lb| lb.container_port.is_some())
+        .map(|lb| (lb.container_name.clone(), lb.container_port))
+        .or_else(|| {
+            service
+                .service_registries
                 .iter()
-                .map(|c| EcsContainer {
-                    name: c.name().unwrap_or_default().to_string(),
-                    runtime_id: c.runtime_id().map(|s| s.to_string()),
-                    last_status: c.last_status().unwrap_or_default().to_string(),
-                    health_status: c.health_status().map(|h| h.as_str().to_string()),
-                })
+                .find(|r| r.container_port.is_some() || r.port.is_some())
+                .map(|r| (r.container_name.clone(), r.container_port.or(r.port)))
+        })
+        .unwrap_or((None, None));
+
+    let tasks = list_tasks(profile, region, cluster, Some(service_name)).await?;
+    let task = tasks
+        .iter()
+        .find(|t| t.last_status == "RUNNING")
+        .ok_or_else(|| format!("No running tasks for service {}", service_name))?;
+
+    resolve_task_endpoint(
+        profile,
+        region,
+        cluster,
+        &task.arn,
+        container_name.as_deref(),
+        container_port,
+    )
+    .await
+}
+
+/// Stop a running task
+pub async fn stop_task(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    task_arn: &str,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    client
+        .stop_task()
+        .cluster(cluster)
+        .task(task_arn)
+        .set_reason(reason.map(|r| r.to_string()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to stop task: {}", e))?;
+
+    Ok(())
+}
+
+/// Resolve the CloudWatch log streams of a task's containers (awslogs driver only)
+pub async fn get_task_log_targets(
+    profile: &str,
+    region: &str,
+    task_definition_arn: &str,
+    task_arn: &str,
+) -> Result<Vec<ContainerLogTarget>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_task_definition()
+        .task_definition(task_definition_arn)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe task definition: {}", e))?;
+
+    let task_id = task_id_from_arn(task_arn);
+
+    let targets = result
+        .task_definition()
+        .map(|td| td.container_definitions())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|cd| {
+            let name = cd.name()?;
+            let options = cd.log_configuration()?.options()?;
+            let log_group = options.get("awslogs-group")?;
+            // Without a stream prefix the stream name is not derivable from the task
+            let prefix = options.get("awslogs-stream-prefix")?;
+
+            Some(ContainerLogTarget {
+                container: name.to_string(),
+                log_group: log_group.clone(),
+                log_stream: format!("{}/{}/{}", prefix, name, task_id),
+                region: options.get("awslogs-region").cloned(),
+            })
+        })
+        .collect();
+
+    Ok(targets)
+}
+
+/// Get the ECS Exec related details of a task and one of its containers
+pub async fn get_task_exec_details(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    task_arn: &str,
+    container: &str,
+) -> Result<TaskExecDetails, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_tasks()
+        .cluster(cluster)
+        .tasks(task_arn)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe task: {}", e))?;
+
+    let task = result
+        .tasks()
+        .first()
+        .ok_or_else(|| format!("Task not found: {}", task_arn))?;
+
+    let target = task
+        .containers()
+        .iter()
+        .find(|c| c.name() == Some(container));
+    let exec_agent = target.and_then(|c| {
+        c.managed_agents()
+            .iter()
+            .find(|a| a.name() == Some(&ManagedAgentName::ExecuteCommandAgent))
+    });
+
+    // A task role override on RunTask takes precedence over the task definition
+    let mut task_role_arn = task
+        .overrides()
+        .and_then(|o| o.task_role_arn())
+        .map(|s| s.to_string());
+
+    if task_role_arn.is_none() {
+        if let Some(td_arn) = task.task_definition_arn() {
+            let td = client
+                .describe_task_definition()
+                .task_definition(td_arn)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to describe task definition: {}", e))?;
+            task_role_arn = td
+                .task_definition()
+                .and_then(|d| d.task_role_arn())
+                .map(|s| s.to_string());
+        }
+    }
+
+    Ok(TaskExecDetails {
+        task_arn: task.task_arn().unwrap_or_default().to_string(),
+        last_status: task.last_status().unwrap_or_default().to_string(),
+        launch_type: task.launch_type().map(|lt| lt.as_str().to_string()),
+        platform_version: task.platform_version().map(|s| s.to_string()),
+        enable_execute_command: task.enable_execute_command(),
+        task_role_arn,
+        container_status: target.and_then(|c| c.last_status()).map(|s| s.to_string()),
+        exec_agent_status: exec_agent
+            .and_then(|a| a.last_status())
+            .map(|s| s.to_string()),
+        exec_agent_reason: exec_agent.and_then(|a| a.reason()).map(|s| s.to_string()),
+    })
+}
+
+/// Get the execute command configuration of a cluster
+pub async fn get_cluster_exec_config(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+) -> Result<ClusterExecConfig, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_clusters()
+        .clusters(cluster)
+        .include(ClusterField::Configurations)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe cluster: {}", e))?;
+
+    let exec_config = result
+        .clusters()
+        .first()
+        .and_then(|c| c.configuration())
+        .and_then(|c| c.execute_command_configuration());
+
+    let Some(exec_config) = exec_config else {
+        return Ok(ClusterExecConfig::default());
+    };
+
+    let log_config = exec_config.log_configuration();
+
+    Ok(ClusterExecConfig {
+        kms_key_id: exec_config.kms_key_id().map(|s| s.to_string()),
+        logging: exec_config.logging().map(|l| l.as_str().to_string()),
+        cloudwatch_log_group: log_config
+            .and_then(|l| l.cloud_watch_log_group_name())
+            .map(|s| s.to_string()),
+        s3_bucket: log_config
+            .and_then(|l| l.s3_bucket_name())
+            .map(|s| s.to_string()),
+    })
+}
+
+/// List the EC2 container instances of a cluster, linked to their EC2 instances
+pub async fn list_container_instances(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+) -> Result<Vec<EcsContainerInstance>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let mut arns = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client.list_container_instances().cluster(cluster);
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list container instances: {}", e))?;
+
+        arns.extend(result.container_instance_arns().to_vec());
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    if arns.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Describe container instances (max 100 at a time)
+    let mut container_instances = Vec::new();
+
+    for chunk in arns.chunks(100) {
+        let result = client
+            .describe_container_instances()
+            .cluster(cluster)
+            .set_container_instances(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to describe container instances: {}", e))?;
+
+        for ci in result.container_instances() {
+            let resource = |resources: &[aws_sdk_ecs::types::Resource], name: &str| {
+                resources
+                    .iter()
+                    .find(|r| r.name() == Some(name))
+                    .map(|r| r.integer_value())
+                    .unwrap_or(0)
+            };
+
+            container_instances.push(EcsContainerInstance {
+                arn: ci.container_instance_arn().unwrap_or_default().to_string(),
+                ec2_instance_id: ci.ec2_instance_id().map(|s| s.to_string()),
+                status: ci.status().unwrap_or_default().to_string(),
+                agent_connected: ci.agent_connected(),
+                agent_version: ci
+                    .version_info()
+                    .and_then(|v| v.agent_version())
+                    .map(|s| s.to_string()),
+                docker_version: ci
+                    .version_info()
+                    .and_then(|v| v.docker_version())
+                    .map(|s| s.to_string()),
+                capacity_provider_name: ci.capacity_provider_name().map(|s| s.to_string()),
+                registered_cpu: resource(ci.registered_resources(), "CPU"),
+                remaining_cpu: resource(ci.remaining_resources(), "CPU"),
+                registered_memory: resource(ci.registered_resources(), "MEMORY"),
+                remaining_memory: resource(ci.remaining_resources(), "MEMORY"),
+                running_tasks_count: ci.running_tasks_count(),
+                pending_tasks_count: ci.pending_tasks_count(),
+                ec2_instance: None,
+            });
+        }
+    }
+
+    // Link each container instance to its EC2 instance
+    let instance_ids: Vec<String> = container_instances
+        .iter()
+        .filter_map(|ci| ci.ec2_instance_id.clone())
+        .collect();
+
+    match get_instances_by_id(profile, region, &instance_ids).await {
+        Ok(instances) => {
+            let by_id: HashMap<String, Ec2Instance> = instances
+                .into_iter()
+                .map(|i| (i.instance_id.clone(), i))
                 .collect();
+            for ci in &mut container_instances {
+                ci.ec2_instance = ci
+                    .ec2_instance_id
+                    .as_ref()
+                    .and_then(|id| by_id.get(id).cloned());
+            }
+        }
+        // The container instance list is still useful without EC2 details
+        Err(e) => tracing::warn!("Failed to describe container instance hosts: {}", e),
+    }
+
+    Ok(container_instances)
+}
+
+/// List the capacity providers attached to a cluster
+pub async fn list_capacity_providers(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+) -> Result<Vec<EcsCapacityProvider>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_clusters()
+        .clusters(cluster)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe cluster: {}", e))?;
+
+    let Some(cluster_info) = result.clusters().first() else {
+        return Err(format!("Cluster not found: {}", cluster));
+    };
 
-            EcsTask {
-                arn: t.task_arn().unwrap_or_default().to_string(),
-                task_definition_arn: t.task_definition_arn().unwrap_or_default().to_string(),
-                cluster_arn: t.cluster_arn().unwrap_or_default().to_string(),
-                last_status: t.last_status().unwrap_or_default().to_string(),
-                desired_status: t.desired_status().unwrap_or_default().to_string(),
-                launch_type: t.launch_type().map(|lt| lt.as_str().to_string()),
-                containers,
-                enable_execute_command: t.enable_execute_command(),
+    let names = cluster_info.capacity_providers().to_vec();
+    if names.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let default_strategy = cluster_info.default_capacity_provider_strategy();
+
+    let result = client
+        .describe_capacity_providers()
+        .set_capacity_providers(Some(names))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe capacity providers: {}", e))?;
+
+    let providers = result
+        .capacity_providers()
+        .iter()
+        .map(|cp| {
+            let name = cp.name().unwrap_or_default().to_string();
+            let strategy = default_strategy
+                .iter()
+                .find(|s| s.capacity_provider() == name);
+            let asg = cp.auto_scaling_group_provider();
+            let scaling = asg.and_then(|a| a.managed_scaling());
+
+            EcsCapacityProvider {
+                arn: cp.capacity_provider_arn().map(|s| s.to_string()),
+                status: cp.status().map(|s| s.as_str().to_string()),
+                auto_scaling_group_arn: asg.map(|a| a.auto_scaling_group_arn().to_string()),
+                managed_scaling_status: scaling
+                    .and_then(|m| m.status())
+                    .map(|s| s.as_str().to_string()),
+                target_capacity: scaling.and_then(|m| m.target_capacity()),
+                managed_termination_protection: asg
+                    .and_then(|a| a.managed_termination_protection())
+                    .map(|p| p.as_str().to_string()),
+                default_weight: strategy.map(|s| s.weight()),
+                default_base: strategy.map(|s| s.base()),
+                name,
             }
         })
         .collect();
 
-    Ok(tasks)
+    Ok(providers)
+}
+
+/// Describe services with their events and deployments
+pub async fn describe_service_snapshots(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_names: &[String],
+) -> Result<Vec<EcsServiceSnapshot>, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let mut snapshots = Vec::new();
+
+    // Describe services (max 10 at a time)
+    for chunk in service_names.chunks(10) {
+        let result = client
+            .describe_services()
+            .cluster(cluster)
+            .set_services(Some(chunk.to_vec()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to describe services: {}", e))?;
+
+        for s in result.services() {
+            let to_millis =
+                |d: &aws_sdk_ecs::primitives::DateTime| (d.as_secs_f64() * 1000.0) as i64;
+
+            let events = s
+                .events()
+                .iter()
+                .map(|e| EcsServiceEvent {
+                    id: e.id().unwrap_or_default().to_string(),
+                    created_at: e.created_at().map(to_millis),
+                    message: e.message().unwrap_or_default().to_string(),
+                })
+                .collect();
+
+            let deployments = s
+                .deployments()
+                .iter()
+                .map(|d| EcsDeployment {
+                    id: d.id().unwrap_or_default().to_string(),
+                    status: d.status().unwrap_or_default().to_string(),
+                    task_definition: d.task_definition().map(|s| s.to_string()),
+                    rollout_state: d.rollout_state().map(|r| r.as_str().to_string()),
+                    rollout_state_reason: d.rollout_state_reason().map(|s| s.to_string()),
+                    desired_count: d.desired_count(),
+                    running_count: d.running_count(),
+                    pending_count: d.pending_count(),
+                    failed_tasks: d.failed_tasks(),
+                    created_at: d.created_at().map(to_millis),
+                    updated_at: d.updated_at().map(to_millis),
+                })
+                .collect();
+
+            snapshots.push(EcsServiceSnapshot {
+                service: to_ecs_service(s),
+                pending_count: s.pending_count(),
+                events,
+                deployments,
+            });
+        }
+    }
+
+    Ok(snapshots)
+}
+
+/// Describe a single cluster
+pub async fn describe_cluster(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+) -> Result<EcsCluster, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_clusters()
+        .clusters(cluster)
+        .include(ClusterField::Settings)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe cluster: {}", e))?;
+
+    result
+        .clusters()
+        .first()
+        .map(to_ecs_cluster)
+        .ok_or_else(|| format!("Cluster not found: {}", cluster))
+}
+
+/// Describe a single service
+pub async fn describe_service(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_name: &str,
+) -> Result<EcsService, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .describe_services()
+        .cluster(cluster)
+        .services(service_name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe service: {}", e))?;
+
+    result
+        .services()
+        .first()
+        .map(to_ecs_service)
+        .ok_or_else(|| format!("Service not found: {}", service_name))
+}
+
+/// Turn execute command on or off for a service, optionally forcing a new deployment
+pub async fn update_service_execute_command(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_name: &str,
+    enable: bool,
+    force_new_deployment: bool,
+) -> Result<EcsService, String> {
+    let client = create_ecs_client(profile, region).await?;
+
+    let result = client
+        .update_service()
+        .cluster(cluster)
+        .service(service_name)
+        .enable_execute_command(enable)
+        .force_new_deployment(force_new_deployment)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to update service: {}", e))?;
+
+    result
+        .service()
+        .map(to_ecs_service)
+        .ok_or_else(|| format!("Service not found: {}", service_name))
+}
+
+/// Convert an SDK cluster into our serializable cluster model
+fn to_ecs_cluster(c: &aws_sdk_ecs::types::Cluster) -> EcsCluster {
+    let container_insights = c
+        .settings()
+        .iter()
+        .find(|s| s.name() == Some(&ClusterSettingName::ContainerInsights))
+        .and_then(|s| s.value())
+        .map(|v| v.to_string());
+
+    EcsCluster {
+        arn: c.cluster_arn().unwrap_or_default().to_string(),
+        name: c.cluster_name().unwrap_or_default().to_string(),
+        status: c.status().unwrap_or_default().to_string(),
+        running_tasks_count: c.running_tasks_count(),
+        services_count: c.active_services_count(),
+        container_insights,
+    }
+}
+
+/// Convert an SDK service into our serializable service model
+fn to_ecs_service(s: &aws_sdk_ecs::types::Service) -> EcsService {
+    let rollout_state = s
+        .deployments()
+        .iter()
+        .find(|d| d.status() == Some("PRIMARY"))
+        .and_then(|d| d.rollout_state())
+        .map(|r| r.as_str().to_string());
+
+    let load_balancers = s
+        .load_balancers()
+        .iter()
+        .map(|lb| EcsLoadBalancer {
+            target_group_arn: lb.target_group_arn().map(|s| s.to_string()),
+            load_balancer_name: lb.load_balancer_name().map(|s| s.to_string()),
+            container_name: lb.container_name().map(|s| s.to_string()),
+            container_port: lb.container_port(),
+        })
+        .collect();
+
+    let service_registries = s
+        .service_registries()
+        .iter()
+        .map(|r| EcsServiceRegistry {
+            registry_arn: r.registry_arn().map(|s| s.to_string()),
+            port: r.port(),
+            container_name: r.container_name().map(|s| s.to_string()),
+            container_port: r.container_port(),
+        })
+        .collect();
+
+    let service_connect = s
+        .deployments()
+        .iter()
+        .find(|d| d.status() == Some("PRIMARY"))
+        .and_then(|d| d.service_connect_configuration())
+        .map(|sc| EcsServiceConnect {
+            enabled: sc.enabled(),
+            namespace: sc.namespace().map(|s| s.to_string()),
+            services: sc
+                .services()
+                .iter()
+                .map(|svc| EcsServiceConnectService {
+                    port_name: svc.port_name().to_string(),
+                    discovery_name: svc.discovery_name().map(|s| s.to_string()),
+                    ingress_port_override: svc.ingress_port_override(),
+                    client_aliases: svc
+                        .client_aliases()
+                        .iter()
+                        .map(|a| EcsServiceConnectAlias {
+                            port: a.port(),
+                            dns_name: a.dns_name().map(|s| s.to_string()),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        });
+
+    EcsService {
+        arn: s.service_arn().unwrap_or_default().to_string(),
+        name: s.service_name().unwrap_or_default().to_string(),
+        cluster_arn: s.cluster_arn().unwrap_or_default().to_string(),
+        status: s.status().unwrap_or_default().to_string(),
+        desired_count: s.desired_count(),
+        running_count: s.running_count(),
+        launch_type: s.launch_type().map(|lt| lt.as_str().to_string()),
+        enable_execute_command: s.enable_execute_command(),
+        rollout_state,
+        load_balancers,
+        service_registries,
+        service_connect,
+    }
+}
+
+/// Convert an SDK task into our serializable task model
+fn to_ecs_task(t: &aws_sdk_ecs::types::Task) -> EcsTask {
+    let containers = t
+        .containers()
+        .iter()
+        .map(|c| EcsContainer {
+            name: c.name().unwrap_or_default().to_string(),
+            runtime_id: c.runtime_id().map(|s| s.to_string()),
+            last_status: c.last_status().unwrap_or_default().to_string(),
+            health_status: c.health_status().map(|h| h.as_str().to_string()),
+            exit_code: c.exit_code(),
+            reason: c.reason().map(|s| s.to_string()),
+            exec_agent_status: c
+                .managed_agents()
+                .iter()
+                .find(|a| a.name() == Some(&ManagedAgentName::ExecuteCommandAgent))
+                .and_then(|a| a.last_status())
+                .map(|s| s.to_string()),
+        })
+        .collect();
+
+    EcsTask {
+        arn: t.task_arn().unwrap_or_default().to_string(),
+        task_definition_arn: t.task_definition_arn().unwrap_or_default().to_string(),
+        cluster_arn: t.cluster_arn().unwrap_or_default().to_string(),
+        last_status: t.last_status().unwrap_or_default().to_string(),
+        desired_status: t.desired_status().unwrap_or_default().to_string(),
+        launch_type: t.launch_type().map(|lt| lt.as_str().to_string()),
+        containers,
+        enable_execute_command: t.enable_execute_command(),
+        stopped_reason: t.stopped_reason().map(|s| s.to_string()),
+        group: t.group().map(|s| s.to_string()),
+        started_by: t.started_by().map(|s| s.to_string()),
+        started_at: t.started_at().map(|d| (d.as_secs_f64() * 1000.0) as i64),
+    }
 }
 
 /// Discover all ECS resources (clusters, services, tasks)
@@ -244,18 +1468,25 @@ pub async fn discover_ecs_resources(
 
     let mut services: HashMap<String, Vec<EcsService>> = HashMap::new();
     let mut tasks: HashMap<String, Vec<EcsTask>> = HashMap::new();
+    let mut standalone_tasks: HashMap<String, Vec<EcsTaskGroup>> = HashMap::new();
 
     for cluster in &clusters {
         // Get services for this cluster
         let cluster_services = list_services(profile, region, &cluster.arn).await?;
         services.insert(cluster.arn.clone(), cluster_services.clone());
 
-        // Get tasks for each service
-        for service in &cluster_services {
-            let service_tasks =
-                list_tasks(profile, region, &cluster.arn, Some(&service.name)).await?;
-            let key = format!("{}:{}", cluster.arn, service.name);
-            tasks.insert(key, service_tasks);
+        // Describe the cluster's tasks once and sort them into services and standalone
+        // task groups. Services are still worth showing when this fails.
+        match list_cluster_tasks(profile, region, &cluster.arn).await {
+            Ok(cluster_tasks) => {
+                let (mut by_service, standalone) = split_cluster_tasks(cluster_tasks);
+                for service in &cluster_services {
+                    let key = format!("{}:{}", cluster.arn, service.name);
+                    tasks.insert(key, by_service.remove(&service.name).unwrap_or_default());
+                }
+                standalone_tasks.insert(cluster.arn.clone(), group_standalone_tasks(standalone));
+            }
+            Err(e) => tracing::warn!("Failed to list tasks of cluster {}: {}", cluster.name, e),
         }
     }
 
@@ -263,5 +1494,66 @@ pub async fn discover_ecs_resources(
         clusters,
         services,
         tasks,
+        standalone_tasks,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(arn: &str, group: Option<&str>, started_by: Option<&str>) -> EcsTask {
+        EcsTask {
+            arn: arn.to_string(),
+            task_definition_arn: String::new(),
+            cluster_arn: String::new(),
+            last_status: "RUNNING".to_string(),
+            desired_status: "RUNNING".to_string(),
+            launch_type: None,
+            containers: vec![],
+            enable_execute_command: false,
+            stopped_reason: None,
+            group: group.map(|s| s.to_string()),
+            started_by: started_by.map(|s| s.to_string()),
+            started_at: None,
+        }
+    }
+
+    #[test]
+    fn test_ecs_ssm_target() {
+        assert_eq!(
+            ecs_ssm_target(
+                "arn:aws:ecs:us-east-1:123456789012:cluster/prod",
+                "arn:aws:ecs:us-east-1:123456789012:task/prod/0a1b2c",
+                "0a1b2c-1234567890"
+            ),
+            "ecs:prod_0a1b2c_0a1b2c-1234567890"
+        );
+        assert_eq!(
+            ecs_ssm_target("prod", "0a1b2c", "0a1b2c-1234567890"),
+            "ecs:prod_0a1b2c_0a1b2c-1234567890"
+        );
+    }
+
+    #[test]
+    fn test_split_and_group_cluster_tasks() {
+        let tasks = vec![
+            task("a", Some("family:cron"), Some("events-rule/nightly")),
+            task("b", None, Some("ecs-svc/123")),
+            task("c", Some("family:cron"), None),
+            task("d", None, None),
+            task("e", Some("service:web"), Some("ecs-svc/456")),
+            task("f", Some("service:web"), None),
+        ];
+
+        let (by_service, standalone) = split_cluster_tasks(tasks);
+        assert_eq!(by_service.len(), 1);
+        assert_eq!(by_service["web"].len(), 2);
+        assert_eq!(standalone.len(), 4);
+
+        let groups = group_standalone_tasks(standalone);
+        let names: Vec<&str> = groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["ecs-svc/123", "family:cron", "ungrouped"]);
+        assert_eq!(groups[1].tasks.len(), 2);
+    }
+}
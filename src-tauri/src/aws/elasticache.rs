@@ -0,0 +1,218 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_elasticache::Client as ElastiCacheClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Redis/Valkey replication group (cluster mode enabled or disabled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElastiCacheReplicationGroup {
+    pub id: String,
+    pub description: Option<String>,
+    pub engine: Option<String>,
+    pub status: String,
+    /// Primary endpoint, cluster mode disabled only
+    pub primary_endpoint: Option<String>,
+    pub reader_endpoint: Option<String>,
+    /// Configuration endpoint, cluster mode enabled only
+    pub configuration_endpoint: Option<String>,
+    pub port: Option<i32>,
+    pub cluster_mode: bool,
+    pub transit_encryption: bool,
+    pub vpc_id: Option<String>,
+    pub member_clusters: Vec<String>,
+}
+
+/// Cache cluster that is not part of a replication group (Memcached or single-node Redis)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElastiCacheCluster {
+    pub id: String,
+    pub engine: String,
+    pub engine_version: Option<String>,
+    pub node_type: Option<String>,
+    pub status: String,
+    /// Configuration endpoint for Memcached, node endpoint otherwise
+    pub endpoint: Option<String>,
+    pub port: Option<i32>,
+    pub transit_encryption: bool,
+    pub vpc_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElastiCacheResources {
+    pub replication_groups: Vec<ElastiCacheReplicationGroup>,
+    pub clusters: Vec<ElastiCacheCluster>,
+}
+
+/// Create an ElastiCache client with the specified profile and region
+async fn create_elasticache_client(
+    profile: &str,
+    region: &str,
+) -> Result<ElastiCacheClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(ElastiCacheClient::new(&config))
+}
+
+/// Map cache subnet group names to their VPC
+async fn get_subnet_group_vpcs(
+    client: &ElastiCacheClient,
+) -> Result<HashMap<String, String>, String> {
+    let mut vpcs = HashMap::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_cache_subnet_groups();
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list cache subnet groups: {}", e))?;
+
+        for group in result.cache_subnet_groups() {
+            if let (Some(name), Some(vpc_id)) = (group.cache_subnet_group_name(), group.vpc_id()) {
+                vpcs.insert(name.to_string(), vpc_id.to_string());
+            }
+        }
+
+        marker = result.marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(vpcs)
+}
+
+/// Discover ElastiCache replication groups and standalone cache clusters
+pub async fn discover_elasticache_resources(
+    profile: &str,
+    region: &str,
+) -> Result<ElastiCacheResources, String> {
+    let client = create_elasticache_client(profile, region).await?;
+    let subnet_vpcs = get_subnet_group_vpcs(&client).await?;
+
+    // Cache clusters carry the subnet group, replication groups inherit it from their members
+    let mut cache_clusters = Vec::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_cache_clusters().show_cache_node_info(true);
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list cache clusters: {}", e))?;
+
+        cache_clusters.extend(result.cache_clusters().iter().cloned());
+
+        marker = result.marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    let cluster_vpc = |cluster: &aws_sdk_elasticache::types::CacheCluster| {
+        cluster
+            .cache_subnet_group_name()
+            .and_then(|name| subnet_vpcs.get(name))
+            .cloned()
+    };
+
+    let member_vpcs: HashMap<&str, String> = cache_clusters
+        .iter()
+        .filter_map(|c| Some((c.cache_cluster_id()?, cluster_vpc(c)?)))
+        .collect();
+
+    let mut replication_groups = Vec::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_replication_groups();
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list replication groups: {}", e))?;
+
+        for group in result.replication_groups() {
+            let node_group = group.node_groups().first();
+            let primary = node_group.and_then(|n| n.primary_endpoint());
+            let configuration = group.configuration_endpoint();
+            let member_clusters: Vec<String> = group.member_clusters().to_vec();
+
+            replication_groups.push(ElastiCacheReplicationGroup {
+                id: group.replication_group_id().unwrap_or_default().to_string(),
+                description: group.description().map(|s| s.to_string()),
+                engine: group.engine().map(|s| s.to_string()),
+                status: group.status().unwrap_or_default().to_string(),
+                primary_endpoint: primary.and_then(|e| e.address()).map(|s| s.to_string()),
+                reader_endpoint: node_group
+                    .and_then(|n| n.reader_endpoint())
+                    .and_then(|e| e.address())
+                    .map(|s| s.to_string()),
+                configuration_endpoint: configuration
+                    .and_then(|e| e.address())
+                    .map(|s| s.to_string()),
+                port: configuration.or(primary).and_then(|e| e.port()),
+                cluster_mode: group.cluster_enabled().unwrap_or(false),
+                transit_encryption: group.transit_encryption_enabled().unwrap_or(false),
+                vpc_id: member_clusters
+                    .iter()
+                    .find_map(|m| member_vpcs.get(m.as_str()))
+                    .cloned(),
+                member_clusters,
+            });
+        }
+
+        marker = result.marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    let mut clusters: Vec<ElastiCacheCluster> = cache_clusters
+        .iter()
+        .filter(|c| c.replication_group_id().is_none())
+        .map(|c| {
+            let endpoint = c
+                .configuration_endpoint()
+                .or_else(|| c.cache_nodes().first().and_then(|n| n.endpoint()));
+
+            ElastiCacheCluster {
+                id: c.cache_cluster_id().unwrap_or_default().to_string(),
+                engine: c.engine().unwrap_or_default().to_string(),
+                engine_version: c.engine_version().map(|s| s.to_string()),
+                node_type: c.cache_node_type().map(|s| s.to_string()),
+                status: c.cache_cluster_status().unwrap_or_default().to_string(),
+                endpoint: endpoint.and_then(|e| e.address()).map(|s| s.to_string()),
+                port: endpoint.and_then(|e| e.port()),
+                transit_encryption: c.transit_encryption_enabled().unwrap_or(false),
+                vpc_id: cluster_vpc(c),
+            }
+        })
+        .collect();
+
+    replication_groups.sort_by(|a, b| a.id.cmp(&b.id));
+    clusters.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(ElastiCacheResources {
+        replication_groups,
+        clusters,
+    })
+}
@@ -0,0 +1,74 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_iam::Client as IamClient;
+use serde::{Deserialize, Serialize};
+
+/// Result of simulating a single IAM action for a principal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicySimulationResult {
+    pub action: String,
+    pub resource: Option<String>,
+    pub decision: String,
+    pub allowed: bool,
+}
+
+/// Create an IAM client with the specified profile and region
+async fn create_iam_client(profile: &str, region: &str) -> Result<IamClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(IamClient::new(&config))
+}
+
+/// Simulate the policies attached to a principal (user or role) for a set of actions
+pub async fn simulate_principal_actions(
+    profile: &str,
+    region: &str,
+    principal_arn: &str,
+    actions: &[&str],
+    resource_arn: Option<&str>,
+) -> Result<Vec<PolicySimulationResult>, String> {
+    let client = create_iam_client(profile, region).await?;
+
+    let mut results = Vec::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .simulate_principal_policy()
+            .policy_source_arn(principal_arn)
+            .set_action_names(Some(actions.iter().map(|a| a.to_string()).collect()));
+
+        if let Some(resource) = resource_arn {
+            request = request.resource_arns(resource);
+        }
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to simulate IAM policy: {}", e))?;
+
+        for r in result.evaluation_results() {
+            let decision = r.eval_decision().as_str().to_string();
+            results.push(PolicySimulationResult {
+                action: r.eval_action_name().to_string(),
+                resource: r.eval_resource_name().map(|s| s.to_string()),
+                allowed: decision == "allowed",
+                decision,
+            });
+        }
+
+        marker = result.marker().map(|s| s.to_string());
+        if !result.is_truncated() || marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(results)
+}
@@ -0,0 +1,46 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_ec2instanceconnect::Client as InstanceConnectClient;
+
+/// How long a key pushed with EC2 Instance Connect is accepted
+pub const PUBLIC_KEY_TTL_SECS: u64 = 60;
+
+/// Create an EC2 Instance Connect client with the specified profile and region
+async fn create_instance_connect_client(
+    profile: &str,
+    region: &str,
+) -> Result<InstanceConnectClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(InstanceConnectClient::new(&config))
+}
+
+/// Push an OpenSSH public key to an instance's metadata for `os_user`.
+/// sshd accepts it for `PUBLIC_KEY_TTL_SECS`, so connect right after.
+pub async fn send_ssh_public_key(
+    profile: &str,
+    region: &str,
+    instance_id: &str,
+    os_user: &str,
+    public_key: &str,
+) -> Result<(), String> {
+    let client = create_instance_connect_client(profile, region).await?;
+
+    let result = client
+        .send_ssh_public_key()
+        .instance_id(instance_id)
+        .instance_os_user(os_user)
+        .ssh_public_key(public_key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send SSH public key: {}", e))?;
+
+    if !result.success() {
+        return Err("EC2 Instance Connect rejected the SSH public key".to_string());
+    }
+
+    Ok(())
+}
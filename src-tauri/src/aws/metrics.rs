@@ -0,0 +1,327 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_cloudwatch::primitives::DateTime;
+use aws_sdk_cloudwatch::types::{Dimension, Metric, MetricDataQuery, MetricStat, ScanBy};
+use aws_sdk_cloudwatch::Client as CloudWatchClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::ecs::describe_cluster;
+
+/// Default window when no start time is given (3 hours)
+const DEFAULT_WINDOW_MS: i64 = 3 * 60 * 60 * 1000;
+
+/// Keep charts readable by capping the number of points per series
+const MAX_POINTS_PER_SERIES: i64 = 360;
+
+/// A single time series, with timestamps (ms) and values in matching order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSeries {
+    pub id: String,
+    pub label: String,
+    pub unit: String,
+    pub timestamps: Vec<i64>,
+    pub values: Vec<f64>,
+}
+
+/// Where ECS utilization metrics were read from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EcsMetricSource {
+    ContainerInsights,
+    AwsEcs,
+}
+
+/// CPU/memory utilization for an ECS cluster or service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsUtilizationMetrics {
+    pub cluster_name: String,
+    pub service_name: Option<String>,
+    pub source: EcsMetricSource,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub period_secs: i32,
+    pub series: Vec<MetricSeries>,
+}
+
+/// Create a CloudWatch (metrics) client with the specified profile and region
+async fn create_cloudwatch_metrics_client(
+    profile: &str,
+    region: &str,
+) -> Result<CloudWatchClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(CloudWatchClient::new(&config))
+}
+
+/// Pick a period (multiple of 60s) that keeps the series under MAX_POINTS_PER_SERIES
+fn auto_period_secs(start_time: i64, end_time: i64) -> i32 {
+    let window_secs = ((end_time - start_time) / 1000).max(60);
+    let period = (window_secs / MAX_POINTS_PER_SERIES).max(60);
+    // Round up to a whole minute
+    (((period + 59) / 60) * 60) as i32
+}
+
+fn build_metric_stat(
+    namespace: &str,
+    metric_name: &str,
+    dimensions: &[(&str, &str)],
+    period_secs: i32,
+    stat: &str,
+) -> Result<MetricStat, String> {
+    let dimensions = dimensions
+        .iter()
+        .map(|(name, value)| {
+            Dimension::builder()
+                .name(*name)
+                .value(*value)
+                .build()
+                .map_err(|e| format!("Invalid metric dimension: {}", e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    MetricStat::builder()
+        .metric(
+            Metric::builder()
+                .namespace(namespace)
+                .metric_name(metric_name)
+                .set_dimensions(Some(dimensions))
+                .build(),
+        )
+        .period(period_secs)
+        .stat(stat)
+        .build()
+        .map_err(|e| format!("Invalid metric query: {}", e))
+}
+
+fn stat_query(
+    id: &str,
+    label: &str,
+    stat: MetricStat,
+    return_data: bool,
+) -> Result<MetricDataQuery, String> {
+    MetricDataQuery::builder()
+        .id(id)
+        .label(label)
+        .metric_stat(stat)
+        .return_data(return_data)
+        .build()
+        .map_err(|e| format!("Invalid metric query: {}", e))
+}
+
+fn expression_query(id: &str, label: &str, expression: &str) -> Result<MetricDataQuery, String> {
+    MetricDataQuery::builder()
+        .id(id)
+        .label(label)
+        .expression(expression)
+        .return_data(true)
+        .build()
+        .map_err(|e| format!("Invalid metric query: {}", e))
+}
+
+/// Queries for utilization percentages from Container Insights (utilized / reserved)
+fn container_insights_queries(
+    dimensions: &[(&str, &str)],
+    period_secs: i32,
+) -> Result<Vec<MetricDataQuery>, String> {
+    const NAMESPACE: &str = "ECS/ContainerInsights";
+
+    let mut queries = Vec::new();
+    for (resource, prefix) in [("Cpu", "cpu"), ("Memory", "memory")] {
+        for stat in ["Average", "Maximum"] {
+            let suffix = stat.to_lowercase();
+            let utilized = format!("{}_utilized_{}", prefix, suffix);
+            let reserved = format!("{}_reserved_{}", prefix, suffix);
+
+            queries.push(stat_query(
+                &utilized,
+                &utilized,
+                build_metric_stat(
+                    NAMESPACE,
+                    &format!("{}Utilized", resource),
+                    dimensions,
+                    period_secs,
+                    stat,
+                )?,
+                false,
+            )?);
+            // Reserved capacity only changes on scaling, so the average is always what we divide by
+            queries.push(stat_query(
+                &reserved,
+                &reserved,
+                build_metric_stat(
+                    NAMESPACE,
+                    &format!("{}Reserved", resource),
+                    dimensions,
+                    period_secs,
+                    "Average",
+                )?,
+                false,
+            )?);
+            queries.push(expression_query(
+                &format!("{}_{}", prefix, suffix),
+                &format!("{} utilization ({})", resource, suffix),
+                &format!("100 * {} / {}", utilized, reserved),
+            )?);
+        }
+    }
+
+    Ok(queries)
+}
+
+/// Queries for utilization percentages from the AWS/ECS namespace
+fn aws_ecs_queries(
+    dimensions: &[(&str, &str)],
+    period_secs: i32,
+) -> Result<Vec<MetricDataQuery>, String> {
+    let mut queries = Vec::new();
+    for (metric_name, resource, prefix) in [
+        ("CPUUtilization", "Cpu", "cpu"),
+        ("MemoryUtilization", "Memory", "memory"),
+    ] {
+        for stat in ["Average", "Maximum"] {
+            let suffix = stat.to_lowercase();
+            queries.push(stat_query(
+                &format!("{}_{}", prefix, suffix),
+                &format!("{} utilization ({})", resource, suffix),
+                build_metric_stat("AWS/ECS", metric_name, dimensions, period_secs, stat)?,
+                true,
+            )?);
+        }
+    }
+
+    Ok(queries)
+}
+
+/// Run GetMetricData and collect every returned series, following pagination
+async fn get_metric_series(
+    client: &CloudWatchClient,
+    queries: Vec<MetricDataQuery>,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<MetricSeries>, String> {
+    let mut series: Vec<MetricSeries> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .get_metric_data()
+            .set_metric_data_queries(Some(queries.clone()))
+            .start_time(DateTime::from_millis(start_time))
+            .end_time(DateTime::from_millis(end_time))
+            .scan_by(ScanBy::TimestampAscending);
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get metric data: {}", e))?;
+
+        for r in result.metric_data_results() {
+            let id = r.id().unwrap_or_default().to_string();
+            let position = *index.entry(id.clone()).or_insert_with(|| {
+                series.push(MetricSeries {
+                    id,
+                    label: r.label().unwrap_or_default().to_string(),
+                    unit: "Percent".to_string(),
+                    timestamps: Vec::new(),
+                    values: Vec::new(),
+                });
+                series.len() - 1
+            });
+
+            let s = &mut series[position];
+            s.timestamps.extend(
+                r.timestamps()
+                    .iter()
+                    .map(|t| (t.as_secs_f64() * 1000.0) as i64),
+            );
+            s.values.extend_from_slice(r.values());
+        }
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(series)
+}
+
+/// Get CPU/memory utilization for a cluster, or a service when `service_name` is set.
+/// Uses Container Insights when it is enabled on the cluster, AWS/ECS otherwise.
+pub async fn get_ecs_utilization(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    service_name: Option<&str>,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    period_secs: Option<i32>,
+) -> Result<EcsUtilizationMetrics, String> {
+    let cluster_info = describe_cluster(profile, region, cluster).await?;
+    let client = create_cloudwatch_metrics_client(profile, region).await?;
+
+    let end_time = end_time.unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let start_time = start_time.unwrap_or(end_time - DEFAULT_WINDOW_MS);
+    let period_secs = period_secs.unwrap_or_else(|| auto_period_secs(start_time, end_time));
+
+    // Services may be passed by ARN, the metric dimension wants the bare name
+    let service_name = service_name.map(|s| s.rsplit('/').next().unwrap_or(s));
+
+    let mut dimensions = vec![("ClusterName", cluster_info.name.as_str())];
+    if let Some(service) = service_name {
+        dimensions.push(("ServiceName", service));
+    }
+
+    let insights_enabled = matches!(
+        cluster_info.container_insights.as_deref(),
+        Some("enabled") | Some("enhanced")
+    );
+
+    let (source, queries) = if insights_enabled {
+        (
+            EcsMetricSource::ContainerInsights,
+            container_insights_queries(&dimensions, period_secs)?,
+        )
+    } else {
+        (
+            EcsMetricSource::AwsEcs,
+            aws_ecs_queries(&dimensions, period_secs)?,
+        )
+    };
+
+    let series = get_metric_series(&client, queries, start_time, end_time).await?;
+
+    Ok(EcsUtilizationMetrics {
+        cluster_name: cluster_info.name,
+        service_name: service_name.map(|s| s.to_string()),
+        source,
+        start_time,
+        end_time,
+        period_secs,
+        series,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_period_secs() {
+        // One hour fits at one-minute resolution
+        assert_eq!(auto_period_secs(0, 60 * 60 * 1000), 60);
+        // One day needs a coarser period, rounded to whole minutes
+        assert_eq!(auto_period_secs(0, 24 * 60 * 60 * 1000), 240);
+        // Degenerate windows still get a valid period
+        assert_eq!(auto_period_secs(1000, 1000), 60);
+    }
+}
@@ -1,8 +1,16 @@
+pub mod autoscaling;
+pub mod cli;
 pub mod cloudwatch;
 pub mod credentials;
 pub mod ec2;
 pub mod ecs;
+pub mod elasticache;
+pub mod iam;
+pub mod instance_connect;
+pub mod metrics;
+pub mod rds;
 pub mod s3;
+pub mod ssm;
 
 pub use credentials::*;
 pub use ec2::*;
@@ -0,0 +1,225 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_rds::auth_token::{AuthTokenGenerator, Config as AuthTokenConfig};
+use aws_sdk_rds::Client as RdsClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdsInstance {
+    pub identifier: String,
+    pub engine: String,
+    pub engine_version: Option<String>,
+    pub instance_class: Option<String>,
+    pub status: String,
+    pub endpoint: Option<String>,
+    pub port: Option<i32>,
+    pub vpc_id: Option<String>,
+    pub availability_zone: Option<String>,
+    /// Aurora cluster the instance belongs to
+    pub cluster_identifier: Option<String>,
+    pub master_username: Option<String>,
+    pub iam_auth_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdsCluster {
+    pub identifier: String,
+    pub engine: String,
+    pub engine_version: Option<String>,
+    pub status: String,
+    /// Writer endpoint
+    pub endpoint: Option<String>,
+    pub reader_endpoint: Option<String>,
+    pub port: Option<i32>,
+    /// Taken from the member instances, clusters don't report it directly
+    pub vpc_id: Option<String>,
+    pub members: Vec<String>,
+    pub master_username: Option<String>,
+    pub iam_auth_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdsResources {
+    pub instances: Vec<RdsInstance>,
+    pub clusters: Vec<RdsCluster>,
+}
+
+/// Create an RDS client with the specified profile and region
+async fn create_rds_client(profile: &str, region: &str) -> Result<RdsClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(RdsClient::new(&config))
+}
+
+/// List RDS DB instances
+pub async fn list_db_instances(profile: &str, region: &str) -> Result<Vec<RdsInstance>, String> {
+    let client = create_rds_client(profile, region).await?;
+
+    let mut instances = Vec::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_db_instances();
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list RDS instances: {}", e))?;
+
+        for db in result.db_instances() {
+            instances.push(RdsInstance {
+                identifier: db.db_instance_identifier().unwrap_or_default().to_string(),
+                engine: db.engine().unwrap_or_default().to_string(),
+                engine_version: db.engine_version().map(|s| s.to_string()),
+                instance_class: db.db_instance_class().map(|s| s.to_string()),
+                status: db.db_instance_status().unwrap_or_default().to_string(),
+                endpoint: db
+                    .endpoint()
+                    .and_then(|e| e.address())
+                    .map(|s| s.to_string()),
+                port: db.endpoint().and_then(|e| e.port()),
+                vpc_id: db
+                    .db_subnet_group()
+                    .and_then(|g| g.vpc_id())
+                    .map(|s| s.to_string()),
+                availability_zone: db.availability_zone().map(|s| s.to_string()),
+                cluster_identifier: db.db_cluster_identifier().map(|s| s.to_string()),
+                master_username: db.master_username().map(|s| s.to_string()),
+                iam_auth_enabled: db.iam_database_authentication_enabled().unwrap_or(false),
+            });
+        }
+
+        marker = result.marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    instances.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    Ok(instances)
+}
+
+/// List RDS/Aurora DB clusters. `instances` is used to fill in the VPC of each cluster.
+pub async fn list_db_clusters(
+    profile: &str,
+    region: &str,
+    instances: &[RdsInstance],
+) -> Result<Vec<RdsCluster>, String> {
+    let client = create_rds_client(profile, region).await?;
+
+    let instance_vpcs: HashMap<&str, &str> = instances
+        .iter()
+        .filter_map(|i| Some((i.identifier.as_str(), i.vpc_id.as_deref()?)))
+        .collect();
+
+    let mut clusters = Vec::new();
+    let mut marker: Option<String> = None;
+
+    loop {
+        let mut request = client.describe_db_clusters();
+
+        if let Some(m) = marker {
+            request = request.marker(m);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list RDS clusters: {}", e))?;
+
+        for cluster in result.db_clusters() {
+            let members: Vec<String> = cluster
+                .db_cluster_members()
+                .iter()
+                .filter_map(|m| m.db_instance_identifier())
+                .map(|s| s.to_string())
+                .collect();
+
+            let vpc_id = members
+                .iter()
+                .find_map(|m| instance_vpcs.get(m.as_str()))
+                .map(|s| s.to_string());
+
+            clusters.push(RdsCluster {
+                identifier: cluster
+                    .db_cluster_identifier()
+                    .unwrap_or_default()
+                    .to_string(),
+                engine: cluster.engine().unwrap_or_default().to_string(),
+                engine_version: cluster.engine_version().map(|s| s.to_string()),
+                status: cluster.status().unwrap_or_default().to_string(),
+                endpoint: cluster.endpoint().map(|s| s.to_string()),
+                reader_endpoint: cluster.reader_endpoint().map(|s| s.to_string()),
+                port: cluster.port(),
+                vpc_id,
+                members,
+                master_username: cluster.master_username().map(|s| s.to_string()),
+                iam_auth_enabled: cluster
+                    .iam_database_authentication_enabled()
+                    .unwrap_or(false),
+            });
+        }
+
+        marker = result.marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    clusters.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    Ok(clusters)
+}
+
+/// Discover RDS instances and clusters
+pub async fn discover_rds_resources(profile: &str, region: &str) -> Result<RdsResources, String> {
+    let instances = list_db_instances(profile, region).await?;
+    let clusters = list_db_clusters(profile, region, &instances).await?;
+
+    Ok(RdsResources {
+        instances,
+        clusters,
+    })
+}
+
+/// How long an RDS IAM authentication token is accepted
+pub const IAM_AUTH_TOKEN_TTL_SECS: i64 = 15 * 60;
+
+/// Mint an RDS IAM authentication token (a SigV4-presigned `connect` URL) for a DB user.
+/// `hostname` and `port` must be the real endpoint, even when connecting through a tunnel.
+pub async fn generate_iam_auth_token(
+    profile: &str,
+    region: &str,
+    hostname: &str,
+    port: u16,
+    username: &str,
+) -> Result<String, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    let generator = AuthTokenGenerator::new(
+        AuthTokenConfig::builder()
+            .hostname(hostname)
+            .port(port as u64)
+            .username(username)
+            .build()
+            .map_err(|e| format!("Invalid IAM auth token settings: {}", e))?,
+    );
+
+    let token = generator
+        .auth_token(&config)
+        .await
+        .map_err(|e| format!("Failed to generate IAM auth token: {}", e))?;
+
+    Ok(token.as_str().to_string())
+}
@@ -0,0 +1,551 @@
+use aws_config::BehaviorVersion;
+use aws_sdk_ssm::types::{
+    DocumentKeyValuesFilter, SessionFilter, SessionFilterKey, SessionState, Target,
+};
+use aws_sdk_ssm::Client as SsmClient;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Instances a Run Command is sent to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommandTargets {
+    Instances {
+        instance_ids: Vec<String>,
+    },
+    /// Every managed instance with tag `key` set to one of `values`
+    Tag {
+        key: String,
+        values: Vec<String>,
+    },
+}
+
+/// Shell the script runs in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandShell {
+    #[default]
+    Shell,
+    PowerShell,
+}
+
+impl CommandShell {
+    pub fn document_name(&self) -> &'static str {
+        match self {
+            Self::Shell => "AWS-RunShellScript",
+            Self::PowerShell => "AWS-RunPowerShellScript",
+        }
+    }
+}
+
+/// Input for sending a Run Command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCommandInput {
+    #[serde(default)]
+    pub shell: CommandShell,
+    pub script: String,
+    pub targets: CommandTargets,
+    pub comment: Option<String>,
+    pub timeout_secs: Option<i32>,
+    /// Instances to run on at once, a number or a percentage ("10", "25%")
+    pub max_concurrency: Option<String>,
+    /// Errors after which the command stops being sent to more instances
+    pub max_errors: Option<String>,
+}
+
+/// Result of a command on one instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandInvocation {
+    pub instance_id: String,
+    pub instance_name: Option<String>,
+    /// Pending, InProgress, Delayed, Success, Cancelled, TimedOut, Failed, Cancelling
+    pub status: String,
+    pub status_details: Option<String>,
+    /// Only set once the invocation finished
+    pub exit_code: Option<i32>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    pub requested_at: Option<i64>,
+}
+
+/// Overall status of a Run Command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunCommandStatus {
+    pub status: String,
+    pub target_count: i32,
+    pub completed_count: i32,
+    pub error_count: i32,
+}
+
+/// Session Manager document available in the account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDocumentSummary {
+    pub name: String,
+    pub display_name: Option<String>,
+    /// "Amazon", the account ID, or the sharing account's ID
+    pub owner: Option<String>,
+    pub document_version: Option<String>,
+    pub platform_types: Vec<String>,
+}
+
+/// Parameters and description of a Session document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDocumentDetail {
+    pub name: String,
+    pub description: Option<String>,
+    pub parameters: Vec<SessionDocumentParameter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDocumentParameter {
+    pub name: String,
+    /// String or StringList
+    pub parameter_type: Option<String>,
+    pub description: Option<String>,
+    pub default_value: Option<String>,
+}
+
+/// Which Session Manager sessions to list
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionListState {
+    Active,
+    /// Terminated and failed sessions, kept by AWS for 30 days
+    History,
+}
+
+/// A Session Manager session, started from this app or anywhere else
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SsmSessionRecord {
+    pub session_id: String,
+    /// Instance ID, or `ecs:...` for ECS Exec sessions
+    pub target: Option<String>,
+    /// ARN of the IAM principal that started the session
+    pub owner: Option<String>,
+    /// Connected, Connecting, Disconnected, Terminated, Terminating, Failed
+    pub status: Option<String>,
+    pub document_name: Option<String>,
+    pub reason: Option<String>,
+    pub started_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub s3_output_url: Option<String>,
+    pub cloudwatch_output_url: Option<String>,
+}
+
+/// Whether a command or invocation status is final
+pub fn is_terminal_status(status: &str) -> bool {
+    matches!(status, "Success" | "Failed" | "Cancelled" | "TimedOut")
+}
+
+/// Invocation outputs requested at once
+const OUTPUT_CONCURRENCY: usize = 8;
+
+/// Create an SSM client with the specified profile and region
+async fn create_ssm_client(profile: &str, region: &str) -> Result<SsmClient, String> {
+    let config = aws_config::defaults(BehaviorVersion::latest())
+        .profile_name(profile)
+        .region(aws_config::Region::new(region.to_string()))
+        .load()
+        .await;
+
+    Ok(SsmClient::new(&config))
+}
+
+/// Send a shell or PowerShell script to instances, returning the command ID
+pub async fn send_command(
+    profile: &str,
+    region: &str,
+    input: &RunCommandInput,
+) -> Result<String, String> {
+    if input.script.trim().is_empty() {
+        return Err("Command is empty".to_string());
+    }
+
+    let client = create_ssm_client(profile, region).await?;
+
+    let mut request = client
+        .send_command()
+        .document_name(input.shell.document_name())
+        .parameters("commands", vec![input.script.clone()])
+        .set_comment(input.comment.clone())
+        .set_timeout_seconds(input.timeout_secs)
+        .set_max_concurrency(input.max_concurrency.clone())
+        .set_max_errors(input.max_errors.clone());
+
+    request = match &input.targets {
+        CommandTargets::Instances { instance_ids } => {
+            if instance_ids.is_empty() {
+                return Err("No target instances".to_string());
+            }
+            request.set_instance_ids(Some(instance_ids.clone()))
+        }
+        CommandTargets::Tag { key, values } => request.targets(
+            Target::builder()
+                .key(format!("tag:{}", key))
+                .set_values(Some(values.clone()))
+                .build(),
+        ),
+    };
+
+    let result = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send command: {}", e))?;
+
+    result
+        .command()
+        .and_then(|c| c.command_id())
+        .map(|s| s.to_string())
+        .ok_or_else(|| "No command ID returned".to_string())
+}
+
+/// Get the overall status of a command
+pub async fn get_command_status(
+    profile: &str,
+    region: &str,
+    command_id: &str,
+) -> Result<RunCommandStatus, String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    let result = client
+        .list_commands()
+        .command_id(command_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get command status: {}", e))?;
+
+    let command = result
+        .commands()
+        .first()
+        .ok_or_else(|| format!("Command not found: {}", command_id))?;
+
+    Ok(RunCommandStatus {
+        status: command
+            .status()
+            .map(|s| s.as_str().to_string())
+            .unwrap_or_default(),
+        target_count: command.target_count(),
+        completed_count: command.completed_count(),
+        error_count: command.error_count(),
+    })
+}
+
+/// List the per-instance invocations of a command, without their output
+pub async fn list_command_invocations(
+    profile: &str,
+    region: &str,
+    command_id: &str,
+) -> Result<Vec<CommandInvocation>, String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    let mut invocations = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client.list_command_invocations().command_id(command_id);
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list command invocations: {}", e))?;
+
+        for invocation in result.command_invocations() {
+            invocations.push(CommandInvocation {
+                instance_id: invocation.instance_id().unwrap_or_default().to_string(),
+                instance_name: invocation
+                    .instance_name()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                status: invocation
+                    .status()
+                    .map(|s| s.as_str().to_string())
+                    .unwrap_or_default(),
+                status_details: invocation.status_details().map(|s| s.to_string()),
+                exit_code: None,
+                stdout: None,
+                stderr: None,
+                requested_at: invocation
+                    .requested_date_time()
+                    .map(|d| (d.as_secs_f64() * 1000.0) as i64),
+            });
+        }
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(invocations)
+}
+
+/// Fill in the exit code and stdout/stderr of a finished invocation.
+/// SSM keeps up to 24,000 characters of each stream.
+async fn fill_invocation_output(
+    client: &SsmClient,
+    command_id: &str,
+    invocation: &mut CommandInvocation,
+) -> Result<(), String> {
+    let result = client
+        .get_command_invocation()
+        .command_id(command_id)
+        .instance_id(&invocation.instance_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get command output: {}", e))?;
+
+    // -1 means the command never ran on the instance
+    invocation.exit_code = Some(result.response_code()).filter(|c| *c >= 0);
+    invocation.stdout = result.standard_output_content().map(|s| s.to_string());
+    invocation.stderr = result
+        .standard_error_content()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    Ok(())
+}
+
+/// Fill in the output of every finished invocation, a few at a time. An instance
+/// whose output can't be fetched (e.g. `InvocationDoesNotExist`) is logged and
+/// left without output instead of failing the rest.
+pub async fn get_invocation_outputs(
+    profile: &str,
+    region: &str,
+    command_id: &str,
+    invocations: &mut [CommandInvocation],
+) -> Result<(), String> {
+    let client = create_ssm_client(profile, region).await?;
+    let semaphore = Arc::new(Semaphore::new(OUTPUT_CONCURRENCY));
+
+    let mut handles = Vec::new();
+    for (index, invocation) in invocations.iter().enumerate() {
+        if !is_terminal_status(&invocation.status) {
+            continue;
+        }
+
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let command_id = command_id.to_string();
+        let mut invocation = invocation.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .map_err(|e| format!("Failed to schedule output request: {}", e))?;
+            fill_invocation_output(&client, &command_id, &mut invocation)
+                .await
+                .map_err(|e| format!("{} ({})", e, invocation.instance_id))?;
+            Ok::<_, String>((index, invocation))
+        }));
+    }
+
+    for handle in handles {
+        let result = handle
+            .await
+            .map_err(|e| format!("Output request failed: {}", e))
+            .and_then(|r| r);
+        match result {
+            Ok((index, invocation)) => invocations[index] = invocation,
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// List the Session documents usable with `start-session`, including the AWS-owned ones
+pub async fn list_session_documents(
+    profile: &str,
+    region: &str,
+) -> Result<Vec<SessionDocumentSummary>, String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    let mut documents = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client.list_documents().filters(
+            DocumentKeyValuesFilter::builder()
+                .key("DocumentType")
+                .values("Session")
+                .build(),
+        );
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list session documents: {}", e))?;
+
+        for document in result.document_identifiers() {
+            documents.push(SessionDocumentSummary {
+                name: document.name().unwrap_or_default().to_string(),
+                display_name: document
+                    .display_name()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                owner: document.owner().map(|s| s.to_string()),
+                document_version: document.document_version().map(|s| s.to_string()),
+                platform_types: document
+                    .platform_types()
+                    .iter()
+                    .map(|p| p.as_str().to_string())
+                    .collect(),
+            });
+        }
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() {
+            break;
+        }
+    }
+
+    documents.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(documents)
+}
+
+/// Get the parameters of a Session document, to fill them in before starting it
+pub async fn describe_session_document(
+    profile: &str,
+    region: &str,
+    name: &str,
+) -> Result<SessionDocumentDetail, String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    let result = client
+        .describe_document()
+        .name(name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to describe session document: {}", e))?;
+
+    let document = result
+        .document()
+        .ok_or_else(|| format!("Document not found: {}", name))?;
+
+    Ok(SessionDocumentDetail {
+        name: name.to_string(),
+        description: document.description().map(|s| s.to_string()),
+        parameters: document
+            .parameters()
+            .iter()
+            .map(|p| SessionDocumentParameter {
+                name: p.name().unwrap_or_default().to_string(),
+                parameter_type: p.r#type().map(|t| t.as_str().to_string()),
+                description: p.description().map(|s| s.to_string()),
+                default_value: p.default_value().map(|s| s.to_string()),
+            })
+            .collect(),
+    })
+}
+
+/// Most sessions returned for the history, which can grow long on busy accounts
+const MAX_SESSION_HISTORY: usize = 500;
+
+/// List active or past Session Manager sessions, optionally for a single target
+pub async fn describe_sessions(
+    profile: &str,
+    region: &str,
+    state: SessionListState,
+    target: Option<&str>,
+) -> Result<Vec<SsmSessionRecord>, String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    let filter = target
+        .map(|t| {
+            SessionFilter::builder()
+                .key(SessionFilterKey::Target)
+                .value(t)
+                .build()
+                .map_err(|e| format!("Invalid session filter: {}", e))
+        })
+        .transpose()?;
+
+    let mut sessions = Vec::new();
+    let mut next_token: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .describe_sessions()
+            .state(match state {
+                SessionListState::Active => SessionState::Active,
+                SessionListState::History => SessionState::History,
+            })
+            .set_filters(filter.clone().map(|f| vec![f]));
+
+        if let Some(token) = next_token {
+            request = request.next_token(token);
+        }
+
+        let result = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list sessions: {}", e))?;
+
+        for session in result.sessions() {
+            sessions.push(SsmSessionRecord {
+                session_id: session.session_id().unwrap_or_default().to_string(),
+                target: session.target().map(|s| s.to_string()),
+                owner: session.owner().map(|s| s.to_string()),
+                status: session.status().map(|s| s.as_str().to_string()),
+                document_name: session.document_name().map(|s| s.to_string()),
+                reason: session
+                    .reason()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                started_at: session
+                    .start_date()
+                    .map(|d| (d.as_secs_f64() * 1000.0) as i64),
+                ended_at: session
+                    .end_date()
+                    .map(|d| (d.as_secs_f64() * 1000.0) as i64),
+                s3_output_url: session
+                    .output_url()
+                    .and_then(|u| u.s3_output_url())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                cloudwatch_output_url: session
+                    .output_url()
+                    .and_then(|u| u.cloud_watch_output_url())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+            });
+        }
+
+        next_token = result.next_token().map(|s| s.to_string());
+        if next_token.is_none() || sessions.len() >= MAX_SESSION_HISTORY {
+            break;
+        }
+    }
+
+    sessions.truncate(MAX_SESSION_HISTORY);
+    sessions.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+    Ok(sessions)
+}
+
+/// Terminate a Session Manager session, including ones started outside this app
+pub async fn terminate_session(
+    profile: &str,
+    region: &str,
+    session_id: &str,
+) -> Result<(), String> {
+    let client = create_ssm_client(profile, region).await?;
+
+    client
+        .terminate_session()
+        .session_id(session_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to terminate session: {}", e))?;
+
+    Ok(())
+}
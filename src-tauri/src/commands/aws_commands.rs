@@ -1,3 +1,4 @@
+use crate::aws::cli::{aws_cli_version, ssm_plugin_version};
 use crate::aws::{
     add_profile, add_sso_profile, delete_profile, get_regions as get_aws_regions,
     list_profiles as get_profiles, profile_exists, AddProfileInput, AddSsoProfileInput,
@@ -19,33 +20,13 @@ pub async fn list_aws_regions() -> Vec<AwsRegion> {
 /// Check if AWS CLI is installed and accessible
 #[tauri::command]
 pub async fn check_aws_cli() -> Result<String, String> {
-    let output = std::process::Command::new("aws")
-        .arg("--version")
-        .output()
-        .map_err(|e| format!("Failed to execute AWS CLI: {}", e))?;
-
-    if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(version.trim().to_string())
-    } else {
-        Err("AWS CLI not found or not working properly".to_string())
-    }
+    aws_cli_version().await
 }
 
 /// Check if session-manager-plugin is installed
 #[tauri::command]
 pub async fn check_ssm_plugin() -> Result<String, String> {
-    let output = std::process::Command::new("session-manager-plugin")
-        .arg("--version")
-        .output()
-        .map_err(|_| "session-manager-plugin not found. Please install it from: https://docs.aws.amazon.com/systems-manager/latest/userguide/session-manager-working-with-install-plugin.html".to_string())?;
-
-    if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).to_string();
-        Ok(version.trim().to_string())
-    } else {
-        Err("session-manager-plugin not working properly".to_string())
-    }
+    ssm_plugin_version().await
 }
 
 /// Get the default region for a profile
@@ -0,0 +1,28 @@
+use crate::aws::ec2::{suggest_bastions, Ec2Instance};
+use crate::aws::elasticache::{discover_elasticache_resources, ElastiCacheResources};
+use crate::aws::rds::{discover_rds_resources, RdsResources};
+
+/// List RDS instances and Aurora clusters with their endpoints
+#[tauri::command]
+pub async fn list_rds_databases(profile: String, region: String) -> Result<RdsResources, String> {
+    discover_rds_resources(&profile, &region).await
+}
+
+/// List ElastiCache replication groups and standalone cache clusters
+#[tauri::command]
+pub async fn list_elasticache_clusters(
+    profile: String,
+    region: String,
+) -> Result<ElastiCacheResources, String> {
+    discover_elasticache_resources(&profile, &region).await
+}
+
+/// Suggest SSM-enabled instances in a VPC to forward database ports through
+#[tauri::command]
+pub async fn suggest_bastion_instances(
+    profile: String,
+    region: String,
+    vpc_id: String,
+) -> Result<Vec<Ec2Instance>, String> {
+    suggest_bastions(&profile, &region, &vpc_id).await
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::aws::autoscaling::{
+    list_auto_scaling_groups as list_groups, set_desired_capacity, set_instance_standby,
+    start_instance_refresh, AutoScalingGroup,
+};
+use crate::aws::ec2::{
+    change_instance_state, describe_instance_detail, get_console_output, get_console_screenshot,
+    ConsoleOutput, InstanceAction, InstanceDetail,
+};
+use crate::aws::ssm::{
+    describe_session_document, describe_sessions, get_invocation_outputs, list_command_invocations,
+    list_session_documents, terminate_session, CommandInvocation, RunCommandInput,
+    SessionDocumentDetail, SessionDocumentSummary, SessionListState, SsmSessionRecord,
+};
+use crate::ec2::power::watch_instance_power;
+use crate::ec2::run_command::{
+    clear_history, get_history_entry, list_history, run_command, CommandHistoryEntry,
+};
+use crate::ec2::ssh::{write_ssh_config, SshConfigEntry};
+
+/// Output of a power action on an instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceActionOutput {
+    /// ID for the `ec2:power:*` progress events
+    pub operation_id: String,
+    /// State reported right after the request (not available for reboots)
+    pub state: Option<String>,
+}
+
+/// Start, stop, reboot or hibernate an EC2 instance.
+/// Progress is emitted until the target state is reached, and with `wait_for_ssm`
+/// until the SSM agent reports Online so a session can be opened.
+#[tauri::command]
+pub async fn change_ec2_instance_state(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    instance_id: String,
+    action: InstanceAction,
+    wait_for_ssm: Option<bool>,
+) -> Result<InstanceActionOutput, String> {
+    let state = change_instance_state(&profile, &region, &instance_id, action).await?;
+
+    let operation_id = uuid::Uuid::new_v4().to_string();
+    watch_instance_power(
+        app_handle,
+        operation_id.clone(),
+        profile,
+        region,
+        instance_id,
+        action,
+        wait_for_ssm.unwrap_or(false),
+    );
+
+    Ok(InstanceActionOutput {
+        operation_id,
+        state,
+    })
+}
+
+/// Describe an instance's network, security groups, volumes, IAM profile and AMI
+#[tauri::command]
+pub async fn describe_ec2_instance_detail(
+    profile: String,
+    region: String,
+    instance_id: String,
+) -> Result<InstanceDetail, String> {
+    describe_instance_detail(&profile, &region, &instance_id).await
+}
+
+/// Get the decoded serial console output of an instance, to diagnose boot failures
+#[tauri::command]
+pub async fn get_ec2_console_output(
+    profile: String,
+    region: String,
+    instance_id: String,
+) -> Result<ConsoleOutput, String> {
+    get_console_output(&profile, &region, &instance_id).await
+}
+
+/// Take a console screenshot of an instance, returned as a base64 JPEG
+#[tauri::command]
+pub async fn get_ec2_console_screenshot(
+    profile: String,
+    region: String,
+    instance_id: String,
+) -> Result<String, String> {
+    get_console_screenshot(&profile, &region, &instance_id).await
+}
+
+/// Send a shell/PowerShell script to instances with SSM Run Command.
+/// Returns the command ID that `ssm:command:*` events are emitted under.
+#[tauri::command]
+pub async fn send_ssm_command(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    input: RunCommandInput,
+) -> Result<String, String> {
+    run_command(app_handle, profile, region, input).await
+}
+
+/// Run a command from the history again, with the same targets and profile
+#[tauri::command]
+pub async fn rerun_ssm_command(
+    app_handle: AppHandle,
+    command_id: String,
+) -> Result<String, String> {
+    let entry = get_history_entry(&command_id)?;
+    run_command(app_handle, entry.profile, entry.region, entry.input).await
+}
+
+/// Get the per-instance results of a command, with output for finished instances
+#[tauri::command]
+pub async fn get_ssm_command_invocations(
+    profile: String,
+    region: String,
+    command_id: String,
+) -> Result<Vec<CommandInvocation>, String> {
+    let mut invocations = list_command_invocations(&profile, &region, &command_id).await?;
+    get_invocation_outputs(&profile, &region, &command_id, &mut invocations).await?;
+    Ok(invocations)
+}
+
+/// List past Run Commands, newest first
+#[tauri::command]
+pub async fn list_ssm_command_history() -> Result<Vec<CommandHistoryEntry>, String> {
+    list_history()
+}
+
+/// Clear the Run Command history
+#[tauri::command]
+pub async fn clear_ssm_command_history() -> Result<(), String> {
+    clear_history()
+}
+
+/// List the Session Manager documents a session can be started with
+#[tauri::command]
+pub async fn list_ssm_session_documents(
+    profile: String,
+    region: String,
+) -> Result<Vec<SessionDocumentSummary>, String> {
+    list_session_documents(&profile, &region).await
+}
+
+/// Get the parameters of a Session document
+#[tauri::command]
+pub async fn describe_ssm_session_document(
+    profile: String,
+    region: String,
+    name: String,
+) -> Result<SessionDocumentDetail, String> {
+    describe_session_document(&profile, &region, &name).await
+}
+
+/// List active or past Session Manager sessions (target, owner, times, status, output),
+/// including ones started outside this app
+#[tauri::command]
+pub async fn list_ssm_sessions(
+    profile: String,
+    region: String,
+    state: SessionListState,
+    target: Option<String>,
+) -> Result<Vec<SsmSessionRecord>, String> {
+    describe_sessions(&profile, &region, state, target.as_deref()).await
+}
+
+/// Terminate any active Session Manager session
+#[tauri::command]
+pub async fn terminate_ssm_session(
+    profile: String,
+    region: String,
+    session_id: String,
+) -> Result<(), String> {
+    terminate_session(&profile, &region, &session_id).await
+}
+
+/// Add or update a `~/.ssh/config` host that reaches an instance through SSM with a
+/// key pushed by EC2 Instance Connect, for scp, rsync and ansible. Returns the block.
+#[tauri::command]
+pub async fn write_ssh_config_entry(entry: SshConfigEntry) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || write_ssh_config(&entry))
+        .await
+        .map_err(|e| format!("Failed to write ssh config: {}", e))?
+}
+
+/// List Auto Scaling groups with their instances (linked to EC2) and latest instance refresh
+#[tauri::command]
+pub async fn list_auto_scaling_groups(
+    profile: String,
+    region: String,
+) -> Result<Vec<AutoScalingGroup>, String> {
+    list_groups(&profile, &region).await
+}
+
+/// Change the desired capacity of an Auto Scaling group
+#[tauri::command]
+pub async fn set_asg_desired_capacity(
+    profile: String,
+    region: String,
+    group_name: String,
+    desired_capacity: i32,
+    honor_cooldown: Option<bool>,
+) -> Result<(), String> {
+    set_desired_capacity(
+        &profile,
+        &region,
+        &group_name,
+        desired_capacity,
+        honor_cooldown.unwrap_or(false),
+    )
+    .await
+}
+
+/// Put instances into Standby so they can be debugged without being replaced,
+/// or return them to service. `decrement_desired_capacity` only applies when
+/// entering Standby; without it a replacement instance is launched.
+#[tauri::command]
+pub async fn set_asg_instance_standby(
+    profile: String,
+    region: String,
+    group_name: String,
+    instance_ids: Vec<String>,
+    standby: bool,
+    decrement_desired_capacity: Option<bool>,
+) -> Result<(), String> {
+    set_instance_standby(
+        &profile,
+        &region,
+        &group_name,
+        instance_ids,
+        standby,
+        decrement_desired_capacity.unwrap_or(true),
+    )
+    .await
+}
+
+/// Start an instance refresh, returning its ID
+#[tauri::command]
+pub async fn start_asg_instance_refresh(
+    profile: String,
+    region: String,
+    group_name: String,
+    min_healthy_percentage: Option<i32>,
+    instance_warmup_secs: Option<i32>,
+) -> Result<String, String> {
+    start_instance_refresh(
+        &profile,
+        &region,
+        &group_name,
+        min_healthy_percentage,
+        instance_warmup_secs,
+    )
+    .await
+}
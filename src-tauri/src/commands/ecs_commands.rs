@@ -0,0 +1,332 @@
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::aws::ecs::{
+    get_service_network_config, list_capacity_providers, list_container_instances,
+    resolve_service_endpoint, resolve_task_endpoint, run_task, stop_task,
+    update_service_execute_command, EcsCapacityProvider, EcsContainerInstance, EcsNetworkConfig,
+    EcsService, RunTaskInput, TaskEndpoint,
+};
+use crate::aws::metrics::{get_ecs_utilization, EcsUtilizationMetrics};
+use crate::ecs::batch::{run_batch_exec, BatchExecInput, BatchExecReport};
+use crate::ecs::copy::{copy_from_container, copy_to_container, ContainerCopyInput, CopyResult};
+use crate::ecs::exec_rollout::watch_exec_rollout;
+use crate::ecs::preflight::{run_exec_preflight, ExecPreflightReport};
+use crate::ecs::run::{EcsTaskRunInfo, TASK_RUN_REGISTRY};
+use crate::ecs::watcher::{ServiceWatchInfo, SERVICE_WATCH_REGISTRY};
+
+/// Run a one-off task from an existing task definition and track it until it stops
+#[tauri::command]
+pub async fn run_ecs_task(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    input: RunTaskInput,
+) -> Result<EcsTaskRunInfo, String> {
+    let task = run_task(&profile, &region, &input).await?;
+    let id = uuid::Uuid::new_v4().to_string();
+
+    Ok(TASK_RUN_REGISTRY.start_tracking(app_handle, id, input.cluster, profile, region, task))
+}
+
+/// Stop the task behind a tracked run
+#[tauri::command]
+pub async fn stop_ecs_task_run(run_id: String) -> Result<(), String> {
+    let run = TASK_RUN_REGISTRY
+        .get_run(&run_id)
+        .ok_or_else(|| format!("Task run not found: {}", run_id))?;
+
+    stop_task(
+        &run.profile,
+        &run.region,
+        &run.cluster,
+        &run.task.arn,
+        Some("Stopped from AWS Connector"),
+    )
+    .await
+}
+
+/// Get a tracked task run
+#[tauri::command]
+pub async fn get_ecs_task_run(run_id: String) -> Result<EcsTaskRunInfo, String> {
+    TASK_RUN_REGISTRY
+        .get_run(&run_id)
+        .ok_or_else(|| format!("Task run not found: {}", run_id))
+}
+
+/// List all tracked task runs
+#[tauri::command]
+pub async fn list_ecs_task_runs() -> Result<Vec<EcsTaskRunInfo>, String> {
+    Ok(TASK_RUN_REGISTRY.list_runs())
+}
+
+/// Get the network configuration of a service (used to pre-fill run task settings)
+#[tauri::command]
+pub async fn get_ecs_service_network_config(
+    profile: String,
+    region: String,
+    cluster: String,
+    service_name: String,
+) -> Result<Option<EcsNetworkConfig>, String> {
+    get_service_network_config(&profile, &region, &cluster, &service_name).await
+}
+
+/// Check whether ECS Exec into a task/container is expected to work
+#[tauri::command]
+pub async fn ecs_exec_preflight(
+    profile: String,
+    region: String,
+    cluster: String,
+    task_arn: String,
+    container: String,
+) -> Result<ExecPreflightReport, String> {
+    run_exec_preflight(&profile, &region, &cluster, &task_arn, &container).await
+}
+
+/// Output from enabling execute command on a service
+#[derive(Debug, Clone, Serialize)]
+pub struct EnableServiceExecOutput {
+    pub service: EcsService,
+    /// ID used in `ecs:exec-rollout:*` events, present when the rollout is being watched
+    pub rollout_id: Option<String>,
+}
+
+/// Enable execute command on a service and optionally redeploy it.
+/// When a new deployment is forced, rollout progress is emitted until every
+/// running task supports exec.
+#[tauri::command]
+pub async fn enable_ecs_service_exec(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    cluster: String,
+    service_name: String,
+    force_new_deployment: bool,
+) -> Result<EnableServiceExecOutput, String> {
+    let service = update_service_execute_command(
+        &profile,
+        &region,
+        &cluster,
+        &service_name,
+        true,
+        force_new_deployment,
+    )
+    .await?;
+
+    // Existing tasks keep their setting, only replaced tasks become exec-capable
+    let rollout_id = force_new_deployment.then(|| {
+        let id = uuid::Uuid::new_v4().to_string();
+        watch_exec_rollout(
+            app_handle,
+            id.clone(),
+            profile,
+            region,
+            cluster,
+            service_name,
+        );
+        id
+    });
+
+    Ok(EnableServiceExecOutput {
+        service,
+        rollout_id,
+    })
+}
+
+/// Get CPU/memory utilization time series for a cluster
+#[tauri::command]
+pub async fn get_ecs_cluster_metrics(
+    profile: String,
+    region: String,
+    cluster: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    period_secs: Option<i32>,
+) -> Result<EcsUtilizationMetrics, String> {
+    get_ecs_utilization(
+        &profile,
+        &region,
+        &cluster,
+        None,
+        start_time,
+        end_time,
+        period_secs,
+    )
+    .await
+}
+
+/// Get CPU/memory utilization time series for a service
+#[tauri::command]
+pub async fn get_ecs_service_metrics(
+    profile: String,
+    region: String,
+    cluster: String,
+    service_name: String,
+    start_time: Option<i64>,
+    end_time: Option<i64>,
+    period_secs: Option<i32>,
+) -> Result<EcsUtilizationMetrics, String> {
+    get_ecs_utilization(
+        &profile,
+        &region,
+        &cluster,
+        Some(&service_name),
+        start_time,
+        end_time,
+        period_secs,
+    )
+    .await
+}
+
+/// List the EC2 container instances of a cluster
+#[tauri::command]
+pub async fn list_ecs_container_instances(
+    profile: String,
+    region: String,
+    cluster: String,
+) -> Result<Vec<EcsContainerInstance>, String> {
+    list_container_instances(&profile, &region, &cluster).await
+}
+
+/// List the capacity providers of a cluster
+#[tauri::command]
+pub async fn list_ecs_capacity_providers(
+    profile: String,
+    region: String,
+    cluster: String,
+) -> Result<Vec<EcsCapacityProvider>, String> {
+    list_capacity_providers(&profile, &region, &cluster).await
+}
+
+/// Start watching services for new events, deployment changes and count drift
+#[tauri::command]
+pub async fn start_ecs_service_watch(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    cluster: String,
+    services: Vec<String>,
+    alert_after_minutes: Option<u32>,
+    poll_interval_secs: Option<u64>,
+) -> Result<ServiceWatchInfo, String> {
+    if services.is_empty() {
+        return Err("No services selected".to_string());
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+
+    Ok(SERVICE_WATCH_REGISTRY.create_session(
+        app_handle,
+        id,
+        cluster,
+        services,
+        profile,
+        region,
+        alert_after_minutes,
+        poll_interval_secs.unwrap_or(15).max(5),
+    ))
+}
+
+/// Stop a service watch session
+#[tauri::command]
+pub async fn stop_ecs_service_watch(watch_id: String) -> Result<(), String> {
+    if SERVICE_WATCH_REGISTRY.stop_session(&watch_id) {
+        Ok(())
+    } else {
+        Err(format!("Watch not found: {}", watch_id))
+    }
+}
+
+/// List all active service watch sessions
+#[tauri::command]
+pub async fn list_ecs_service_watches() -> Result<Vec<ServiceWatchInfo>, String> {
+    Ok(SERVICE_WATCH_REGISTRY.list_sessions())
+}
+
+/// Download a file (or an archive of a directory) from a container over ECS Exec
+#[tauri::command]
+pub async fn ecs_copy_from_container(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    input: ContainerCopyInput,
+) -> Result<CopyResult, String> {
+    let transfer_id = input
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    tokio::task::spawn_blocking(move || {
+        copy_from_container(app_handle, &profile, &region, &transfer_id, &input)
+    })
+    .await
+    .map_err(|e| format!("Copy task failed: {}", e))?
+}
+
+/// Upload a local file into a container over ECS Exec
+#[tauri::command]
+pub async fn ecs_copy_to_container(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    input: ContainerCopyInput,
+) -> Result<CopyResult, String> {
+    let transfer_id = input
+        .transfer_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    tokio::task::spawn_blocking(move || {
+        copy_to_container(app_handle, &profile, &region, &transfer_id, &input)
+    })
+    .await
+    .map_err(|e| format!("Copy task failed: {}", e))?
+}
+
+/// Run one command on many containers in parallel and return a combined report
+#[tauri::command]
+pub async fn ecs_batch_exec(
+    app_handle: AppHandle,
+    profile: String,
+    region: String,
+    input: BatchExecInput,
+) -> Result<BatchExecReport, String> {
+    let batch_id = input
+        .batch_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    run_batch_exec(app_handle, profile, region, batch_id, input).await
+}
+
+/// Resolve the private IP and port of a task for pre-filling a port forward
+#[tauri::command]
+pub async fn get_ecs_task_endpoint(
+    profile: String,
+    region: String,
+    cluster: String,
+    task_arn: String,
+    container_name: Option<String>,
+    container_port: Option<i32>,
+) -> Result<TaskEndpoint, String> {
+    resolve_task_endpoint(
+        &profile,
+        &region,
+        &cluster,
+        &task_arn,
+        container_name.as_deref(),
+        container_port,
+    )
+    .await
+}
+
+/// Resolve the private IP and port of a running task of a service
+#[tauri::command]
+pub async fn get_ecs_service_endpoint(
+    profile: String,
+    region: String,
+    cluster: String,
+    service_name: String,
+) -> Result<TaskEndpoint, String> {
+    resolve_service_endpoint(&profile, &region, &cluster, &service_name).await
+}
@@ -1,11 +1,19 @@
 pub mod aws_commands;
+pub mod database_commands;
+pub mod ec2_commands;
+pub mod ecs_commands;
 pub mod logs_commands;
 pub mod resource_commands;
 pub mod s3_commands;
 pub mod terminal_commands;
+pub mod tunnel_commands;
 
 pub use aws_commands::*;
+pub use database_commands::*;
+pub use ec2_commands::*;
+pub use ecs_commands::*;
 pub use logs_commands::*;
 pub use resource_commands::*;
 pub use s3_commands::*;
 pub use terminal_commands::*;
+pub use tunnel_commands::*;
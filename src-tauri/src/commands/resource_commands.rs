@@ -1,6 +1,7 @@
+use crate::aws::ec2::{group_instances, InstanceFilters, InstanceGroup};
 use crate::aws::{
-    discover_ecs_resources, list_clusters, list_services, list_ssm_instances, list_tasks,
-    Ec2Instance, EcsCluster, EcsResources, EcsService, EcsTask,
+    discover_ecs_resources, list_clusters, list_instances, list_services, list_tasks, Ec2Instance,
+    EcsCluster, EcsResources, EcsService, EcsTask,
 };
 use serde::{Deserialize, Serialize};
 
@@ -16,9 +17,14 @@ pub async fn discover_resources(
     profile: String,
     region: String,
 ) -> Result<DiscoveredResources, String> {
-    // Run ECS and EC2 discovery in parallel
+    // Run ECS and EC2 discovery in parallel. Stopped instances are listed too, so
+    // they can be started from the tree.
+    let ec2_filters = InstanceFilters {
+        all_states: true,
+        ..Default::default()
+    };
     let ecs_future = discover_ecs_resources(&profile, &region);
-    let ec2_future = list_ssm_instances(&profile, &region);
+    let ec2_future = list_instances(&profile, &region, true, &ec2_filters);
 
     let (ecs_result, ec2_result) = tokio::join!(ecs_future, ec2_future);
 
@@ -64,11 +70,24 @@ pub async fn list_ecs_tasks(
     .await
 }
 
-/// List SSM-enabled EC2 instances
+/// List SSM-enabled EC2 instances matching server-side filters (running ones by default)
 #[tauri::command]
 pub async fn list_ec2_instances(
     profile: String,
     region: String,
+    filters: Option<InstanceFilters>,
 ) -> Result<Vec<Ec2Instance>, String> {
-    list_ssm_instances(&profile, &region).await
+    list_instances(&profile, &region, true, &filters.unwrap_or_default()).await
+}
+
+/// List SSM-enabled EC2 instances grouped by tag values, one level per key in `group_by`
+#[tauri::command]
+pub async fn list_ec2_instance_groups(
+    profile: String,
+    region: String,
+    group_by: Vec<String>,
+    filters: Option<InstanceFilters>,
+) -> Result<Vec<InstanceGroup>, String> {
+    let instances = list_instances(&profile, &region, true, &filters.unwrap_or_default()).await?;
+    Ok(group_instances(instances, &group_by))
 }
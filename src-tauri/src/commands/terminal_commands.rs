@@ -4,10 +4,18 @@ use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use uuid::Uuid;
 
+use crate::aws::ecs::ecs_ssm_target;
+use crate::aws::instance_connect::send_ssh_public_key;
+use crate::ec2::session::ssm_session_args;
+use crate::ec2::ssh::{ensure_keypair, ssh_args};
+use crate::ecs::exec::ecs_exec_args;
 use crate::terminal::{
     create_pty_session, resize_pty, start_output_stream, write_to_pty, SessionInfo,
     SessionRegistry, SessionStatus, SessionType,
 };
+use crate::tunnels::clients::{client_command, iam_auth_token};
+use crate::tunnels::port_forwarding_args;
+use crate::tunnels::ports::ensure_local_port;
 
 /// Global session registry
 static SESSIONS: Lazy<SessionRegistry> = Lazy::new(SessionRegistry::new);
@@ -18,6 +26,9 @@ pub struct CreateSessionInput {
     pub session_type: SessionType,
     pub title: Option<String>,
     pub shell: Option<String>,
+    /// For port forwarding: use the next free port when the local port is taken
+    #[serde(default)]
+    pub pick_free_port: bool,
 }
 
 /// Output from creating a new terminal session
@@ -25,6 +36,8 @@ pub struct CreateSessionInput {
 pub struct CreateSessionOutput {
     pub session_id: String,
     pub info: SessionInfo,
+    /// Local port actually bound, for port forwarding sessions
+    pub local_port: Option<u16>,
 }
 
 /// Create a new terminal session
@@ -36,28 +49,70 @@ pub async fn terminal_create_session(
     let session_id = Uuid::new_v4().to_string();
     let shell = input.shell.unwrap_or_else(|| "/bin/sh".to_string());
 
+    // Check the local port before spawning, the CLI only fails once a client connects
+    let mut session_type = input.session_type;
+    let local_port = match &mut session_type {
+        SessionType::SsmPortForwarding { local_port, .. }
+        | SessionType::EcsPortForwarding { local_port, .. } => {
+            let (port, pick_free_port) = (*local_port, input.pick_free_port);
+            *local_port =
+                tokio::task::spawn_blocking(move || ensure_local_port(port, pick_free_port))
+                    .await
+                    .map_err(|e| format!("Failed to check local port: {}", e))??;
+            Some(*local_port)
+        }
+        _ => None,
+    };
+
     // Generate title if not provided
-    let title = input.title.unwrap_or_else(|| match &input.session_type {
+    let title = input.title.unwrap_or_else(|| match &session_type {
         SessionType::EcsExec { container, .. } => format!("ECS: {}", container),
-        SessionType::SsmSession { instance_id, .. } => format!("EC2: {}", instance_id),
+        SessionType::SsmSession {
+            instance_id,
+            document,
+            ..
+        } => match document {
+            Some(document) => format!("EC2: {} ({})", instance_id, document.label()),
+            None => format!("EC2: {}", instance_id),
+        },
+        SessionType::InstanceConnectSsh {
+            instance_id,
+            os_user,
+            ..
+        } => format!("SSH: {}@{}", os_user, instance_id),
         SessionType::SsmPortForwarding {
             local_port,
             remote_port,
             ..
         } => format!("Port Forward: {} -> {}", local_port, remote_port),
+        SessionType::EcsPortForwarding {
+            container,
+            local_port,
+            remote_port,
+            ..
+        } => format!(
+            "Port Forward: {} -> {}:{}",
+            local_port, container, remote_port
+        ),
+        SessionType::DatabaseClient { connection, .. } => format!(
+            "{}: localhost:{}",
+            connection.client.program(),
+            connection.local_port
+        ),
         SessionType::Local => "Local Shell".to_string(),
     });
 
     let info = SessionInfo {
         id: session_id.clone(),
         title,
-        session_type: input.session_type.clone(),
+        session_type: session_type.clone(),
         created_at: chrono::Utc::now().timestamp(),
         status: SessionStatus::Starting,
     };
 
     // Build command based on session type
-    let (command, args): (&str, Vec<String>) = match &input.session_type {
+    let mut env: Vec<(String, String)> = vec![];
+    let (command, args): (&str, Vec<String>) = match &session_type {
         SessionType::EcsExec {
             cluster,
             task,
@@ -66,41 +121,39 @@ pub async fn terminal_create_session(
             region,
         } => (
             "aws",
-            vec![
-                "ecs".to_string(),
-                "execute-command".to_string(),
-                "--cluster".to_string(),
-                cluster.clone(),
-                "--task".to_string(),
-                task.clone(),
-                "--container".to_string(),
-                container.clone(),
-                "--interactive".to_string(),
-                "--command".to_string(),
-                shell.clone(),
-                "--profile".to_string(),
-                profile.clone(),
-                "--region".to_string(),
-                region.clone(),
-            ],
+            ecs_exec_args(cluster, task, container, &shell, profile, region),
         ),
         SessionType::SsmSession {
             instance_id,
             profile,
             region,
+            document,
         } => (
             "aws",
-            vec![
-                "ssm".to_string(),
-                "start-session".to_string(),
-                "--target".to_string(),
-                instance_id.clone(),
-                "--profile".to_string(),
-                profile.clone(),
-                "--region".to_string(),
-                region.clone(),
-            ],
+            ssm_session_args(instance_id, document.as_ref(), profile, region),
         ),
+        SessionType::InstanceConnectSsh {
+            instance_id,
+            os_user,
+            profile,
+            region,
+        } => {
+            // The pushed key is only accepted for a minute, so push it right before connecting
+            let keypair = tokio::task::spawn_blocking(ensure_keypair)
+                .await
+                .map_err(|e| format!("Failed to prepare SSH key: {}", e))??;
+            send_ssh_public_key(profile, region, instance_id, os_user, &keypair.public_key).await?;
+            (
+                "ssh",
+                ssh_args(
+                    instance_id,
+                    os_user,
+                    &keypair.private_key_path,
+                    profile,
+                    region,
+                ),
+            )
+        }
         SessionType::SsmPortForwarding {
             instance_id,
             local_port,
@@ -108,50 +161,57 @@ pub async fn terminal_create_session(
             remote_host,
             profile,
             region,
+        } => (
+            "aws",
+            port_forwarding_args(
+                instance_id,
+                *local_port,
+                *remote_port,
+                remote_host.as_deref(),
+                profile,
+                region,
+            ),
+        ),
+        SessionType::EcsPortForwarding {
+            cluster,
+            task,
+            runtime_id,
+            local_port,
+            remote_port,
+            remote_host,
+            profile,
+            region,
+            ..
+        } => (
+            "aws",
+            port_forwarding_args(
+                &ecs_ssm_target(cluster, task, runtime_id),
+                *local_port,
+                *remote_port,
+                remote_host.as_deref(),
+                profile,
+                region,
+            ),
+        ),
+        SessionType::DatabaseClient {
+            connection,
+            iam_auth,
         } => {
-            let mut args = vec![
-                "ssm".to_string(),
-                "start-session".to_string(),
-                "--target".to_string(),
-                instance_id.clone(),
-            ];
-
-            // Choose document based on whether remote_host is specified
-            if let Some(host) = remote_host {
-                // Remote host forwarding (for RDS/ElastiCache endpoints)
-                args.push("--document-name".to_string());
-                args.push("AWS-StartPortForwardingSessionToRemoteHost".to_string());
-                args.push("--parameters".to_string());
-                args.push(format!(
-                    "host={},portNumber={},localPortNumber={}",
-                    host, remote_port, local_port
-                ));
-            } else {
-                // Local port forwarding to the instance itself
-                args.push("--document-name".to_string());
-                args.push("AWS-StartPortForwardingSession".to_string());
-                args.push("--parameters".to_string());
-                args.push(format!(
-                    "portNumber={},localPortNumber={}",
-                    remote_port, local_port
-                ));
-            }
-
-            args.push("--profile".to_string());
-            args.push(profile.clone());
-            args.push("--region".to_string());
-            args.push(region.clone());
-
-            ("aws", args)
+            let mut connection = connection.clone();
+            let token = iam_auth_token(&mut connection, iam_auth.as_ref()).await?;
+            let client = client_command(&connection, token.as_deref());
+            env = client.env;
+            (connection.client.program(), client.args)
         }
         SessionType::Local => (&shell, vec![]),
     };
 
     // Convert args to references
     let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let env_refs: Vec<(&str, &str)> = env.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
 
     // Create the PTY session
-    let mut session = create_pty_session(info.clone(), command, &args_refs, vec![])
+    let mut session = create_pty_session(info.clone(), command, &args_refs, env_refs)
         .map_err(|e| e.to_string())?;
 
     // Take the reader and start streaming output
@@ -169,6 +229,7 @@ pub async fn terminal_create_session(
     Ok(CreateSessionOutput {
         session_id,
         info: final_info,
+        local_port,
     })
 }
 
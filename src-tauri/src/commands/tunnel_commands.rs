@@ -0,0 +1,75 @@
+use tauri::AppHandle;
+
+use crate::terminal::session::{ClientConnection, IamAuthTarget};
+use crate::tunnels::clients::{self, ConnectionInfo};
+use crate::tunnels::ports::{self, LocalPortStatus};
+use crate::tunnels::store::{
+    create_tunnel, delete_tunnel, get_tunnel, list_tunnels, update_tunnel, TunnelDefinition,
+    TunnelInput,
+};
+use crate::tunnels::{manager::TunnelState, TUNNEL_MANAGER};
+
+/// List saved port-forward tunnels
+#[tauri::command]
+pub async fn list_saved_tunnels() -> Result<Vec<TunnelDefinition>, String> {
+    list_tunnels()
+}
+
+/// Save a new tunnel, or update an existing one when `tunnel_id` is given
+#[tauri::command]
+pub async fn save_tunnel(
+    tunnel_id: Option<String>,
+    input: TunnelInput,
+) -> Result<TunnelDefinition, String> {
+    match tunnel_id {
+        Some(id) => update_tunnel(&id, input),
+        None => create_tunnel(input),
+    }
+}
+
+/// Delete a saved tunnel, stopping it if it is running
+#[tauri::command]
+pub async fn delete_saved_tunnel(tunnel_id: String) -> Result<(), String> {
+    TUNNEL_MANAGER.stop(&tunnel_id);
+    delete_tunnel(&tunnel_id)
+}
+
+/// Start a saved tunnel and keep it connected
+#[tauri::command]
+pub async fn start_tunnel(app_handle: AppHandle, tunnel_id: String) -> Result<TunnelState, String> {
+    let tunnel = get_tunnel(&tunnel_id)?;
+    Ok(TUNNEL_MANAGER.start(app_handle, tunnel))
+}
+
+/// Stop a running tunnel
+#[tauri::command]
+pub async fn stop_tunnel(tunnel_id: String) -> Result<(), String> {
+    if TUNNEL_MANAGER.stop(&tunnel_id) {
+        Ok(())
+    } else {
+        Err(format!("Tunnel not running: {}", tunnel_id))
+    }
+}
+
+/// List the state of all started tunnels
+#[tauri::command]
+pub async fn list_tunnel_states() -> Result<Vec<TunnelState>, String> {
+    Ok(TUNNEL_MANAGER.list_states())
+}
+
+/// Check whether a local port is free, and who holds it if not
+#[tauri::command]
+pub async fn check_local_port(port: u16) -> Result<LocalPortStatus, String> {
+    tokio::task::spawn_blocking(move || ports::check_local_port(port))
+        .await
+        .map_err(|e| format!("Failed to check local port: {}", e))
+}
+
+/// Build client connection strings for a tunnel, minting an RDS IAM auth token if requested
+#[tauri::command]
+pub async fn get_tunnel_connection_info(
+    connection: ClientConnection,
+    iam_auth: Option<IamAuthTarget>,
+) -> Result<ConnectionInfo, String> {
+    clients::connection_info(connection, iam_auth.as_ref()).await
+}
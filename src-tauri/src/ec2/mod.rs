@@ -0,0 +1,4 @@
+pub mod power;
+pub mod run_command;
+pub mod session;
+pub mod ssh;
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::aws::ec2::{get_instances_by_id, Ec2Instance, InstanceAction};
+use crate::util::poll::{Polled, Poller};
+
+/// Phase of waiting for an instance power action to finish
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstancePowerPhase {
+    /// Waiting for the instance to reach the target state
+    Transitioning,
+    /// Running, waiting for the SSM agent to report Online
+    WaitingForAgent,
+    Completed,
+    Failed,
+    TimedOut,
+}
+
+/// Progress of a start/stop/reboot/hibernate action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstancePowerProgress {
+    pub id: String,
+    pub instance_id: String,
+    pub action: InstanceAction,
+    pub phase: InstancePowerPhase,
+    pub state: Option<String>,
+    pub ssm_ping_status: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Where an instance is relative to the goal of an action
+fn evaluate_phase(
+    action: InstanceAction,
+    instance: &Ec2Instance,
+    wait_for_ssm: bool,
+) -> InstancePowerPhase {
+    match instance.state.as_str() {
+        state if state == action.target_state() => {
+            let needs_agent = wait_for_ssm && action.target_state() == "running";
+            if needs_agent && instance.ssm_ping_status.as_deref() != Some("Online") {
+                InstancePowerPhase::WaitingForAgent
+            } else {
+                InstancePowerPhase::Completed
+            }
+        }
+        // Terminal states an instance never leaves for the target
+        "terminated" | "shutting-down" => InstancePowerPhase::Failed,
+        _ => InstancePowerPhase::Transitioning,
+    }
+}
+
+/// Spawn a background task that waits for an instance to reach the target state of
+/// `action`, and with `wait_for_ssm` for its SSM agent to come Online
+pub fn watch_instance_power(
+    app: AppHandle,
+    id: String,
+    profile: String,
+    region: String,
+    instance_id: String,
+    action: InstanceAction,
+    wait_for_ssm: bool,
+) {
+    tokio::spawn(async move {
+        run_instance_power_watch(app, id, profile, region, instance_id, action, wait_for_ssm).await;
+    });
+}
+
+async fn run_instance_power_watch(
+    app: AppHandle,
+    id: String,
+    profile: String,
+    region: String,
+    instance_id: String,
+    action: InstanceAction,
+    wait_for_ssm: bool,
+) {
+    let poller = Poller::new(Duration::from_secs(5), Duration::from_secs(15 * 60));
+
+    let progress_event = format!("ec2:power:progress:{}", id);
+    let done_event = format!("ec2:power:done:{}", id);
+
+    // A reboot never leaves "running", give the agent time to drop its connection first
+    if action == InstanceAction::Reboot {
+        tokio::time::sleep(Duration::from_secs(30)).await;
+    }
+
+    let instance_ids = [instance_id.clone()];
+    // State from the last successful poll, reported if polling times out
+    let mut last_progress: Option<InstancePowerProgress> = None;
+
+    loop {
+        let polled = poller
+            .poll(get_instances_by_id(&profile, &region, &instance_ids))
+            .await;
+
+        let instance = match polled {
+            Polled::Ready(instances) => instances.into_iter().next(),
+            Polled::Failed(e) => {
+                tracing::warn!("Instance state poll failed for {}: {}", instance_id, e);
+                let _ = app.emit(&format!("ec2:power:error:{}", id), &e);
+                continue;
+            }
+            Polled::TimedOut(e) => {
+                let mut progress = last_progress.unwrap_or(InstancePowerProgress {
+                    id: id.clone(),
+                    instance_id: instance_id.clone(),
+                    action,
+                    phase: InstancePowerPhase::Transitioning,
+                    state: None,
+                    ssm_ping_status: None,
+                    message: None,
+                });
+                progress.phase = InstancePowerPhase::TimedOut;
+                progress.message = Some(e);
+                let _ = app.emit(&done_event, &progress);
+                break;
+            }
+        };
+
+        let mut progress = InstancePowerProgress {
+            id: id.clone(),
+            instance_id: instance_id.clone(),
+            action,
+            phase: InstancePowerPhase::Failed,
+            state: instance.as_ref().map(|i| i.state.clone()),
+            ssm_ping_status: instance.as_ref().and_then(|i| i.ssm_ping_status.clone()),
+            message: None,
+        };
+
+        match &instance {
+            Some(instance) => progress.phase = evaluate_phase(action, instance, wait_for_ssm),
+            None => progress.message = Some(format!("Instance not found: {}", instance_id)),
+        }
+
+        if progress.phase == InstancePowerPhase::Failed && progress.message.is_none() {
+            progress.message = Some(format!(
+                "Instance is {}",
+                progress.state.as_deref().unwrap_or("unknown")
+            ));
+        } else if matches!(
+            progress.phase,
+            InstancePowerPhase::Transitioning | InstancePowerPhase::WaitingForAgent
+        ) && poller.expired()
+        {
+            progress.phase = InstancePowerPhase::TimedOut;
+            progress.message = Some(format!(
+                "Instance did not become ready within {} minutes",
+                poller.timeout().as_secs() / 60
+            ));
+        }
+
+        match progress.phase {
+            InstancePowerPhase::Transitioning | InstancePowerPhase::WaitingForAgent => {
+                if let Err(e) = app.emit(&progress_event, &progress) {
+                    tracing::error!("Failed to emit instance power progress: {}", e);
+                }
+                last_progress = Some(progress);
+            }
+            _ => {
+                let _ = app.emit(&done_event, &progress);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(state: &str, ping: Option<&str>) -> Ec2Instance {
+        Ec2Instance {
+            state: state.to_string(),
+            ssm_ping_status: ping.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_evaluate_phase() {
+        use InstancePowerPhase::*;
+
+        let start = InstanceAction::Start;
+        assert_eq!(
+            evaluate_phase(start, &instance("pending", None), true),
+            Transitioning
+        );
+        assert_eq!(
+            evaluate_phase(start, &instance("running", Some("ConnectionLost")), true),
+            WaitingForAgent
+        );
+        assert_eq!(
+            evaluate_phase(start, &instance("running", Some("ConnectionLost")), false),
+            Completed
+        );
+        assert_eq!(
+            evaluate_phase(start, &instance("running", Some("Online")), true),
+            Completed
+        );
+        assert_eq!(
+            evaluate_phase(start, &instance("terminated", None), true),
+            Failed
+        );
+
+        // The agent is never waited for when stopping
+        let hibernate = InstanceAction::Hibernate;
+        assert_eq!(
+            evaluate_phase(hibernate, &instance("stopping", None), true),
+            Transitioning
+        );
+        assert_eq!(
+            evaluate_phase(hibernate, &instance("stopped", None), true),
+            Completed
+        );
+    }
+}
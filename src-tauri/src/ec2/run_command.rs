@@ -0,0 +1,287 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::aws::ssm::{
+    get_command_status, get_invocation_outputs, is_terminal_status, list_command_invocations,
+    send_command, CommandInvocation, RunCommandInput, RunCommandStatus,
+};
+use crate::util::fs::{app_config_path, write_atomic};
+use crate::util::poll::{Polled, Poller};
+
+const HISTORY_FILE: &str = "run_command_history.json";
+
+/// Number of past commands kept in the history
+const MAX_HISTORY: usize = 100;
+
+/// Serializes read-modify-write cycles on the history file
+static HISTORY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// A command sent with Run Command, kept so it can be re-run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandHistoryEntry {
+    pub command_id: String,
+    pub profile: String,
+    pub region: String,
+    pub input: RunCommandInput,
+    pub started_at: i64,
+    pub finished_at: Option<i64>,
+    /// Last known overall status
+    pub status: Option<RunCommandStatus>,
+}
+
+fn history_path() -> Result<PathBuf, String> {
+    app_config_path(HISTORY_FILE)
+}
+
+fn read_history() -> Result<Vec<CommandHistoryEntry>, String> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read command history: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse command history: {}", e))
+}
+
+fn write_history(entries: &[CommandHistoryEntry]) -> Result<(), String> {
+    let path = history_path()?;
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize command history: {}", e))?;
+
+    write_atomic(&path, content.as_bytes())
+        .map_err(|e| format!("Failed to save command history: {}", e))
+}
+
+/// Add an entry at the front of the history, dropping the oldest past `MAX_HISTORY`
+fn push_entry(entries: &mut Vec<CommandHistoryEntry>, entry: CommandHistoryEntry) {
+    entries.retain(|e| e.command_id != entry.command_id);
+    entries.insert(0, entry);
+    entries.truncate(MAX_HISTORY);
+}
+
+fn update_entry(command_id: &str, f: impl FnOnce(&mut CommandHistoryEntry)) {
+    let _guard = HISTORY_LOCK.lock();
+
+    let result = read_history().and_then(|mut entries| {
+        if let Some(entry) = entries.iter_mut().find(|e| e.command_id == command_id) {
+            f(entry);
+            write_history(&entries)?;
+        }
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to update command history: {}", e);
+    }
+}
+
+/// List past Run Commands, newest first
+pub fn list_history() -> Result<Vec<CommandHistoryEntry>, String> {
+    let _guard = HISTORY_LOCK.lock();
+    read_history()
+}
+
+/// Remove all Run Command history
+pub fn clear_history() -> Result<(), String> {
+    let _guard = HISTORY_LOCK.lock();
+    write_history(&[])
+}
+
+/// Look up a past command to run it again
+pub fn get_history_entry(command_id: &str) -> Result<CommandHistoryEntry, String> {
+    list_history()?
+        .into_iter()
+        .find(|e| e.command_id == command_id)
+        .ok_or_else(|| format!("Command not found in history: {}", command_id))
+}
+
+/// Send a Run Command, record it in the history and stream per-instance results
+/// as `ssm:command:invocation:{command_id}` until `ssm:command:done:{command_id}`
+pub async fn run_command(
+    app: AppHandle,
+    profile: String,
+    region: String,
+    input: RunCommandInput,
+) -> Result<String, String> {
+    let command_id = send_command(&profile, &region, &input).await?;
+
+    {
+        let _guard = HISTORY_LOCK.lock();
+        let mut entries = read_history().unwrap_or_default();
+        push_entry(
+            &mut entries,
+            CommandHistoryEntry {
+                command_id: command_id.clone(),
+                profile: profile.clone(),
+                region: region.clone(),
+                input,
+                started_at: chrono::Utc::now().timestamp_millis(),
+                finished_at: None,
+                status: None,
+            },
+        );
+        if let Err(e) = write_history(&entries) {
+            tracing::warn!("Failed to save command history: {}", e);
+        }
+    }
+
+    let id = command_id.clone();
+    tokio::spawn(async move {
+        poll_command(app, profile, region, id).await;
+    });
+
+    Ok(command_id)
+}
+
+async fn poll_command(app: AppHandle, profile: String, region: String, command_id: String) {
+    // Commands time out on their own, this only guards against polling forever
+    let poller = Poller::new(Duration::from_secs(3), Duration::from_secs(6 * 60 * 60));
+
+    let invocation_event = format!("ssm:command:invocation:{}", command_id);
+    let error_event = format!("ssm:command:error:{}", command_id);
+
+    // Last status emitted per instance
+    let mut reported: HashMap<String, String> = HashMap::new();
+    let mut last_status: Option<RunCommandStatus> = None;
+
+    loop {
+        let invocations = match poller
+            .poll(list_command_invocations(&profile, &region, &command_id))
+            .await
+        {
+            Polled::Ready(invocations) => invocations,
+            Polled::Failed(e) => {
+                tracing::warn!("Run Command poll failed for {}: {}", command_id, e);
+                let _ = app.emit(&error_event, &e);
+                continue;
+            }
+            Polled::TimedOut(e) => {
+                let _ = app.emit(&error_event, &e);
+                finish_command(&app, &command_id, last_status, &reported);
+                break;
+            }
+        };
+
+        let mut changed: Vec<CommandInvocation> = invocations
+            .into_iter()
+            .filter(|i| reported.get(&i.instance_id) != Some(&i.status))
+            .collect();
+        if let Err(e) = get_invocation_outputs(&profile, &region, &command_id, &mut changed).await {
+            tracing::warn!("{}", e);
+        }
+
+        for invocation in changed {
+            reported.insert(invocation.instance_id.clone(), invocation.status.clone());
+            if let Err(e) = app.emit(&invocation_event, &invocation) {
+                tracing::error!("Failed to emit command invocation: {}", e);
+            }
+        }
+
+        let status = get_command_status(&profile, &region, &command_id).await;
+        let status = match poller.check(status) {
+            Polled::Ready(status) => status,
+            Polled::Failed(e) => {
+                tracing::warn!("Run Command status failed for {}: {}", command_id, e);
+                continue;
+            }
+            Polled::TimedOut(e) => {
+                let _ = app.emit(&error_event, &e);
+                finish_command(&app, &command_id, last_status, &reported);
+                break;
+            }
+        };
+
+        let all_reported = reported.values().all(|s| is_terminal_status(s));
+        let finished = is_terminal_status(&status.status) && all_reported;
+
+        if finished || poller.expired() {
+            finish_command(&app, &command_id, Some(status), &reported);
+            break;
+        }
+        last_status = Some(status);
+    }
+}
+
+/// Record the final status in the history and emit `ssm:command:done:{command_id}`.
+/// Without a known status, one is derived from the per-instance statuses seen.
+fn finish_command(
+    app: &AppHandle,
+    command_id: &str,
+    status: Option<RunCommandStatus>,
+    reported: &HashMap<String, String>,
+) {
+    let status = status.unwrap_or_else(|| RunCommandStatus {
+        status: "Unknown".to_string(),
+        target_count: reported.len() as i32,
+        completed_count: reported.values().filter(|s| is_terminal_status(s)).count() as i32,
+        error_count: reported
+            .values()
+            .filter(|s| is_terminal_status(s) && s.as_str() != "Success")
+            .count() as i32,
+    });
+
+    let finished_at = chrono::Utc::now().timestamp_millis();
+    let final_status = status.clone();
+    update_entry(command_id, |entry| {
+        entry.finished_at = Some(finished_at);
+        entry.status = Some(final_status);
+    });
+
+    let _ = app.emit(&format!("ssm:command:done:{}", command_id), &status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::ssm::{CommandShell, CommandTargets};
+
+    fn entry(command_id: &str) -> CommandHistoryEntry {
+        CommandHistoryEntry {
+            command_id: command_id.to_string(),
+            profile: "default".to_string(),
+            region: "us-east-1".to_string(),
+            input: RunCommandInput {
+                shell: CommandShell::Shell,
+                script: "uptime".to_string(),
+                targets: CommandTargets::Instances {
+                    instance_ids: vec!["i-1".to_string()],
+                },
+                comment: None,
+                timeout_secs: None,
+                max_concurrency: None,
+                max_errors: None,
+            },
+            started_at: 0,
+            finished_at: None,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_push_entry_keeps_newest_first_and_caps() {
+        let mut entries = Vec::new();
+        for i in 0..MAX_HISTORY + 5 {
+            push_entry(&mut entries, entry(&format!("cmd-{}", i)));
+        }
+
+        assert_eq!(entries.len(), MAX_HISTORY);
+        assert_eq!(entries[0].command_id, format!("cmd-{}", MAX_HISTORY + 4));
+        assert_eq!(entries.last().unwrap().command_id, "cmd-5");
+
+        // Re-adding an entry moves it to the front instead of duplicating it
+        push_entry(&mut entries, entry("cmd-50"));
+        assert_eq!(entries.len(), MAX_HISTORY);
+        assert_eq!(entries[0].command_id, "cmd-50");
+        assert_eq!(
+            entries.iter().filter(|e| e.command_id == "cmd-50").count(),
+            1
+        );
+    }
+}
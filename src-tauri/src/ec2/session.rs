@@ -0,0 +1,69 @@
+use crate::terminal::session::SessionDocument;
+
+/// Build the `aws ssm start-session` arguments, with the default shell when no
+/// document is given
+pub fn ssm_session_args(
+    instance_id: &str,
+    document: Option<&SessionDocument>,
+    profile: &str,
+    region: &str,
+) -> Vec<String> {
+    let mut args = vec![
+        "ssm".to_string(),
+        "start-session".to_string(),
+        "--target".to_string(),
+        instance_id.to_string(),
+    ];
+
+    if let Some(document) = document {
+        args.push("--document-name".to_string());
+        args.push(document.document_name().to_string());
+        if let Some(parameters) = document.parameters_json() {
+            args.push("--parameters".to_string());
+            args.push(parameters);
+        }
+    }
+
+    args.push("--profile".to_string());
+    args.push(profile.to_string());
+    args.push("--region".to_string());
+    args.push(region.to_string());
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_ssm_session_args() {
+        let document = SessionDocument::InteractiveCommand {
+            command: r#"sudo -u app bash -c "cd /srv, exec bash""#.to_string(),
+        };
+        let args = ssm_session_args("i-1", Some(&document), "default", "us-east-1");
+        assert_eq!(
+            &args[4..6],
+            ["--document-name", "AWS-StartInteractiveCommand"]
+        );
+        assert_eq!(args[6], "--parameters");
+        assert_eq!(
+            args[7],
+            r#"{"command":["sudo -u app bash -c \"cd /srv, exec bash\""]}"#
+        );
+
+        let custom = SessionDocument::Custom {
+            name: "Team-PsqlConsole".to_string(),
+            parameters: BTreeMap::new(),
+        };
+        let args = ssm_session_args("i-1", Some(&custom), "default", "us-east-1");
+        assert_eq!(args[5], "Team-PsqlConsole");
+        assert!(!args.contains(&"--parameters".to_string()));
+
+        assert_eq!(
+            ssm_session_args("i-1", None, "default", "us-east-1").len(),
+            8
+        );
+    }
+}
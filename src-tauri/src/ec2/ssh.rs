@@ -0,0 +1,414 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use ssh_key::rand_core::OsRng;
+use ssh_key::{Algorithm, LineEnding, PrivateKey};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::util::fs::{app_config_path, write_atomic};
+use crate::util::shell::shell_quote;
+
+const KEY_DIR: &str = "ssh";
+const KEY_FILE: &str = "id_ed25519";
+/// Copy of `~/.ssh/config` from before the app's last edit
+const CONFIG_BACKUP_FILE: &str = "config.aws-connector.bak";
+
+/// The key only has to be valid for the minute EC2 Instance Connect accepts it,
+/// but ssh configs written by the app point at it, so it is rotated daily
+const KEY_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Serializes key rotation
+static KEY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// App-managed SSH keypair pushed to instances with EC2 Instance Connect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TempKeyPair {
+    pub private_key_path: PathBuf,
+    /// OpenSSH public key line
+    pub public_key: String,
+}
+
+/// `~/.ssh/config` host reaching an instance over SSM with an Instance Connect key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshConfigEntry {
+    /// Name to `ssh`/`scp`/`rsync` to, e.g. "api-bastion"
+    pub host_alias: String,
+    pub instance_id: String,
+    pub os_user: String,
+    pub profile: String,
+    pub region: String,
+}
+
+fn key_path() -> Result<PathBuf, String> {
+    Ok(app_config_path(KEY_DIR)?.join(KEY_FILE))
+}
+
+/// Get the app's SSH keypair, generating a new one when missing or older than a day
+pub fn ensure_keypair() -> Result<TempKeyPair, String> {
+    // Windows OpenSSH needs an ACL-restricted key file, and the ssh config entries
+    // rely on a POSIX shell in their ProxyCommand
+    if !cfg!(unix) {
+        return Err("SSH through Session Manager is only supported on macOS and Linux".to_string());
+    }
+
+    let _guard = KEY_LOCK.lock();
+
+    let path = key_path()?;
+    let is_fresh = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < KEY_MAX_AGE);
+
+    if is_fresh {
+        match load_keypair(&path) {
+            Ok(keypair) => return Ok(keypair),
+            Err(e) => tracing::warn!("Regenerating SSH key: {}", e),
+        }
+    }
+
+    generate_keypair(&path)
+}
+
+/// Read the keypair from the private key, rewriting the `.pub` file the ssh config
+/// entries push when it does not match (e.g. after an interrupted rotation)
+fn load_keypair(path: &Path) -> Result<TempKeyPair, String> {
+    let public_key = PrivateKey::read_openssh_file(path)
+        .map_err(|e| format!("Failed to read SSH key: {}", e))?
+        .public_key()
+        .to_openssh()
+        .map_err(|e| format!("Failed to encode SSH key: {}", e))?;
+
+    let public_key_path = path.with_extension("pub");
+    let stored = fs::read_to_string(&public_key_path).unwrap_or_default();
+    if stored.trim() != public_key {
+        write_atomic(&public_key_path, format!("{}\n", public_key).as_bytes())
+            .map_err(|e| format!("Failed to save SSH key: {}", e))?;
+    }
+
+    Ok(TempKeyPair {
+        private_key_path: path.to_path_buf(),
+        public_key,
+    })
+}
+
+/// Generate a new keypair. Both files are replaced atomically, the public key first,
+/// so an interrupted rotation leaves a private key `load_keypair` can still use.
+fn generate_keypair(path: &Path) -> Result<TempKeyPair, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)
+        .map_err(|e| format!("Failed to generate SSH key: {}", e))?;
+    let private_key = key
+        .to_openssh(LineEnding::LF)
+        .map_err(|e| format!("Failed to encode SSH key: {}", e))?;
+    let public_key = key
+        .public_key()
+        .to_openssh()
+        .map_err(|e| format!("Failed to encode SSH key: {}", e))?;
+
+    write_atomic(
+        &path.with_extension("pub"),
+        format!("{}\n", public_key).as_bytes(),
+    )
+    .map_err(|e| format!("Failed to save SSH key: {}", e))?;
+    write_private_key(path, private_key.as_bytes())
+        .map_err(|e| format!("Failed to save SSH key: {}", e))?;
+
+    Ok(TempKeyPair {
+        private_key_path: path.to_path_buf(),
+        public_key,
+    })
+}
+
+/// ssh refuses private keys that other users can read
+#[cfg(unix)]
+fn write_private_key(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    // The mode only applies to new files, so write a fresh temp file and rename it over
+    let tmp_path = path.with_extension("tmp");
+    let _ = fs::remove_file(&tmp_path);
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&tmp_path)?
+        .write_all(contents)?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(not(unix))]
+fn write_private_key(_path: &Path, _contents: &[u8]) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// ProxyCommand tunnelling ssh through Session Manager. ssh expands `%h` and `%p`.
+pub fn ssm_proxy_command(profile: &str, region: &str) -> String {
+    format!(
+        "aws ssm start-session --target %h --document-name AWS-StartSSHSession \
+         --parameters portNumber=%p --profile {} --region {}",
+        shell_quote(profile),
+        shell_quote(region)
+    )
+}
+
+/// Build the `ssh` arguments for connecting to an instance through Session Manager
+pub fn ssh_args(
+    instance_id: &str,
+    os_user: &str,
+    private_key_path: &Path,
+    profile: &str,
+    region: &str,
+) -> Vec<String> {
+    vec![
+        "-i".to_string(),
+        private_key_path.display().to_string(),
+        "-o".to_string(),
+        "IdentitiesOnly=yes".to_string(),
+        // Instance IDs are never reused, so the first host key seen can be trusted
+        "-o".to_string(),
+        "StrictHostKeyChecking=accept-new".to_string(),
+        "-o".to_string(),
+        format!("ProxyCommand={}", ssm_proxy_command(profile, region)),
+        format!("{}@{}", os_user, instance_id),
+    ]
+}
+
+/// Host aliases end up in `~/.ssh/config`, so keep them to a safe set of characters
+pub fn validate_host_alias(alias: &str) -> Result<(), String> {
+    let valid = !alias.is_empty()
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid host alias '{}': use letters, digits, '-', '_' and '.'",
+            alias
+        ))
+    }
+}
+
+fn block_markers(alias: &str) -> (String, String) {
+    (
+        format!("# BEGIN aws-connector {}", alias),
+        format!("# END aws-connector {}", alias),
+    )
+}
+
+/// Config block for an entry. The ProxyCommand pushes the public key right before
+/// starting the SSM session, so `ssh`, `scp`, `rsync` and ansible all just work.
+pub fn ssh_config_block(entry: &SshConfigEntry, private_key_path: &Path) -> String {
+    let (begin, end) = block_markers(&entry.host_alias);
+    let key_path = private_key_path.display().to_string();
+
+    let push_key = format!(
+        "aws ec2-instance-connect send-ssh-public-key --instance-id %h --instance-os-user %r \
+         --ssh-public-key {} --profile {} --region {} > /dev/null",
+        shell_quote(&format!("file://{}.pub", key_path)),
+        shell_quote(&entry.profile),
+        shell_quote(&entry.region)
+    );
+    let proxy = format!(
+        "{} && exec {}",
+        push_key,
+        ssm_proxy_command(&entry.profile, &entry.region)
+    );
+
+    [
+        begin,
+        format!("Host {}", entry.host_alias),
+        format!("    HostName {}", entry.instance_id),
+        format!("    User {}", entry.os_user),
+        format!("    IdentityFile \"{}\"", key_path),
+        "    IdentitiesOnly yes".to_string(),
+        "    StrictHostKeyChecking accept-new".to_string(),
+        format!("    ProxyCommand sh -c {}", shell_quote(&proxy)),
+        end,
+    ]
+    .join("\n")
+}
+
+/// Replace the app's block for `alias` in an ssh config, or add it at the top.
+/// ssh uses the first value it finds, so the block has to come before `Host *`.
+pub fn upsert_config_block(config: &str, alias: &str, block: &str) -> String {
+    let (begin, end) = block_markers(alias);
+
+    // Match the whole line, "api" must not find the block of "api-bastion"
+    let existing = config
+        .find(&format!("{}\n", begin))
+        .and_then(|start| Some((start, start + config[start..].find(&end)? + end.len())));
+
+    match existing {
+        Some((start, stop)) => format!("{}{}{}", &config[..start], block, &config[stop..]),
+        None if config.trim().is_empty() => format!("{}\n", block),
+        None => format!("{}\n\n{}", block, config),
+    }
+}
+
+/// ssh refuses configs in a directory other users can write to
+#[cfg(unix)]
+fn create_ssh_dir(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(path)
+}
+
+#[cfg(not(unix))]
+fn create_ssh_dir(path: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(path)
+}
+
+/// Add or update a `~/.ssh/config` host for an instance, returning the written block
+pub fn write_ssh_config(entry: &SshConfigEntry) -> Result<String, String> {
+    validate_host_alias(&entry.host_alias)?;
+    let keypair = ensure_keypair()?;
+    let block = ssh_config_block(entry, &keypair.private_key_path);
+
+    let ssh_dir = dirs::home_dir()
+        .ok_or_else(|| "Could not determine home directory".to_string())?
+        .join(".ssh");
+    create_ssh_dir(&ssh_dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let path = ssh_dir.join("config");
+    let config = match fs::read_to_string(&path) {
+        Ok(config) => config,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Failed to read ssh config: {}", e)),
+    };
+
+    // Keep the previous version, with its permissions, in case the edit isn't wanted
+    if !config.is_empty() {
+        fs::copy(&path, ssh_dir.join(CONFIG_BACKUP_FILE))
+            .map_err(|e| format!("Failed to back up ssh config: {}", e))?;
+    }
+
+    let content = upsert_config_block(&config, &entry.host_alias, &block);
+
+    write_atomic(&path, content.as_bytes())
+        .map_err(|e| format!("Failed to save ssh config: {}", e))?;
+
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> SshConfigEntry {
+        SshConfigEntry {
+            host_alias: "api-bastion".to_string(),
+            instance_id: "i-0abc".to_string(),
+            os_user: "ec2-user".to_string(),
+            profile: "prod".to_string(),
+            region: "eu-west-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ssh_args() {
+        let args = ssh_args(
+            "i-0abc",
+            "ubuntu",
+            Path::new("/keys/id_ed25519"),
+            "my profile",
+            "us-east-1",
+        );
+        assert_eq!(args[..2], ["-i", "/keys/id_ed25519"]);
+        assert!(args.contains(&"ProxyCommand=aws ssm start-session --target %h --document-name AWS-StartSSHSession --parameters portNumber=%p --profile 'my profile' --region 'us-east-1'".to_string()));
+        assert_eq!(args.last().unwrap(), "ubuntu@i-0abc");
+    }
+
+    #[test]
+    fn test_ssh_config_block() {
+        let block = ssh_config_block(
+            &entry(),
+            Path::new("/Users/me/Library/Application Support/k"),
+        );
+        let lines: Vec<&str> = block.lines().collect();
+        assert_eq!(lines[0], "# BEGIN aws-connector api-bastion");
+        assert_eq!(lines[1], "Host api-bastion");
+        assert_eq!(lines[2], "    HostName i-0abc");
+        assert_eq!(
+            lines[4],
+            "    IdentityFile \"/Users/me/Library/Application Support/k\""
+        );
+        assert!(lines[7].starts_with("    ProxyCommand sh -c 'aws ec2-instance-connect"));
+        assert!(lines[7].contains(
+            r"--ssh-public-key '\''file:///Users/me/Library/Application Support/k.pub'\''"
+        ));
+        assert!(lines[7].contains("&& exec aws ssm start-session"));
+        assert_eq!(lines[8], "# END aws-connector api-bastion");
+    }
+
+    #[test]
+    fn test_upsert_config_block() {
+        let block = ssh_config_block(&entry(), Path::new("/k"));
+        let config = "Host *\n    User admin\n";
+
+        let added = upsert_config_block(config, "api-bastion", &block);
+        assert!(added.starts_with("# BEGIN aws-connector api-bastion"));
+        assert!(added.ends_with("Host *\n    User admin\n"));
+
+        let mut moved = entry();
+        moved.instance_id = "i-0def".to_string();
+        let updated = upsert_config_block(
+            &added,
+            "api-bastion",
+            &ssh_config_block(&moved, Path::new("/k")),
+        );
+        assert_eq!(updated.matches("Host api-bastion").count(), 1);
+        let mut other = entry();
+        other.host_alias = "api".to_string();
+        let both = upsert_config_block(&updated, "api", &ssh_config_block(&other, Path::new("/k")));
+        assert_eq!(both.matches("# BEGIN aws-connector").count(), 2);
+
+        assert!(updated.contains("HostName i-0def"));
+        assert!(updated.ends_with("Host *\n    User admin\n"));
+
+        assert_eq!(
+            upsert_config_block("", "api-bastion", &block),
+            format!("{}\n", block)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_keypair_repairs_public_key() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("awsc-ssh-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(KEY_FILE);
+        let generated = generate_keypair(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // A public key left over from an older private key
+        fs::write(path.with_extension("pub"), "ssh-ed25519 AAAAstale\n").unwrap();
+
+        let loaded = load_keypair(&path).unwrap();
+        assert_eq!(loaded.public_key, generated.public_key);
+        let stored = fs::read_to_string(path.with_extension("pub")).unwrap();
+        assert_eq!(stored.trim(), generated.public_key);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_host_alias() {
+        assert!(validate_host_alias("api-bastion.prod").is_ok());
+        assert!(validate_host_alias("").is_err());
+        assert!(validate_host_alias("a b").is_err());
+        assert!(validate_host_alias("x\nHost *").is_err());
+    }
+}
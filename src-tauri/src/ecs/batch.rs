@@ -0,0 +1,385 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+
+use super::exec::{ExecProcess, ExecTarget};
+use crate::util::shell::shell_quote;
+
+const BEGIN_MARKER: &str = "__AWSC_BEGIN__";
+const STDERR_MARKER: &str = "__AWSC_STDERR__";
+const EXIT_MARKER: &str = "__AWSC_EXIT__";
+
+const DEFAULT_CONCURRENCY: usize = 5;
+const MAX_CONCURRENCY: usize = 20;
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 60;
+/// Output kept per stream and target, anything beyond is dropped
+const MAX_OUTPUT_BYTES: usize = 256 * 1024;
+
+/// Input for running one command across many containers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExecInput {
+    pub command: String,
+    pub targets: Vec<ExecTarget>,
+    /// Maximum number of exec sessions running at once (default 5)
+    pub concurrency: Option<usize>,
+    /// Kill a session after this many seconds without output (default 60)
+    pub idle_timeout_secs: Option<u64>,
+    /// ID used in `ecs:batch:result:{id}` events, generated when not provided
+    pub batch_id: Option<String>,
+}
+
+/// Outcome of the command on a single target
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchTargetStatus {
+    /// Exited with status 0
+    Succeeded,
+    /// Exited with a non-zero status
+    Failed,
+    /// The exec session failed or ended without reporting an exit status
+    Error,
+}
+
+/// Output and exit status of the command on a single target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExecTargetResult {
+    pub target: ExecTarget,
+    pub status: BatchTargetStatus,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    /// Empty when the container has no writable temp directory, stderr is then part of stdout
+    pub stderr: String,
+    pub truncated: bool,
+    pub error: Option<String>,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+/// Combined report for a batch exec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchExecReport {
+    pub batch_id: String,
+    pub command: String,
+    pub results: Vec<BatchExecTargetResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub errors: usize,
+    pub started_at: i64,
+    pub finished_at: i64,
+}
+
+/// Wrap the user command so stdout, stderr and the exit status can be told apart
+/// in the single TTY stream an exec session gives us
+fn wrap_command(command: &str) -> String {
+    let script = format!(
+        "e=$(mktemp 2>/dev/null || echo /dev/stdout); echo {begin}; ( {command}\n) </dev/null 2>\"$e\"; c=$?; \
+         echo; echo {stderr}; [ -f \"$e\" ] && {{ cat \"$e\"; echo; rm -f \"$e\"; }}; echo \"{exit} $c\"",
+        begin = BEGIN_MARKER,
+        command = command,
+        stderr = STDERR_MARKER,
+        exit = EXIT_MARKER,
+    );
+    format!("sh -c {}", shell_quote(&script))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputSection {
+    Preamble,
+    Stdout,
+    Stderr,
+    Done,
+}
+
+/// Splits exec session output into stdout, stderr and exit status using the
+/// markers printed by `wrap_command`
+struct BatchOutputParser {
+    section: OutputSection,
+    preamble: Vec<String>,
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+    stdout_bytes: usize,
+    stderr_bytes: usize,
+    truncated: bool,
+    exit_code: Option<i32>,
+}
+
+/// Parsed output of a wrapped command
+#[derive(Debug, PartialEq)]
+struct BatchOutput {
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    truncated: bool,
+    /// Session output seen before the command started, useful when it never did
+    preamble: String,
+}
+
+impl BatchOutputParser {
+    fn new() -> Self {
+        Self {
+            section: OutputSection::Preamble,
+            preamble: Vec::new(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+            stdout_bytes: 0,
+            stderr_bytes: 0,
+            truncated: false,
+            exit_code: None,
+        }
+    }
+
+    /// Feed one output line, returning false once the exit status has been seen
+    fn push_line(&mut self, line: &str) -> bool {
+        let trimmed = line.trim();
+        match self.section {
+            OutputSection::Preamble => {
+                if trimmed == BEGIN_MARKER {
+                    self.section = OutputSection::Stdout;
+                } else if !trimmed.is_empty() {
+                    self.preamble.push(trimmed.to_string());
+                }
+            }
+            OutputSection::Stdout => {
+                if trimmed == STDERR_MARKER {
+                    self.section = OutputSection::Stderr;
+                } else {
+                    Self::keep(
+                        &mut self.stdout,
+                        &mut self.stdout_bytes,
+                        &mut self.truncated,
+                        line,
+                    );
+                }
+            }
+            OutputSection::Stderr => {
+                if let Some(code) = trimmed.strip_prefix(EXIT_MARKER) {
+                    self.exit_code = code.trim().parse().ok();
+                    self.section = OutputSection::Done;
+                } else {
+                    Self::keep(
+                        &mut self.stderr,
+                        &mut self.stderr_bytes,
+                        &mut self.truncated,
+                        line,
+                    );
+                }
+            }
+            OutputSection::Done => {}
+        }
+        self.section != OutputSection::Done
+    }
+
+    fn keep(lines: &mut Vec<String>, bytes: &mut usize, truncated: &mut bool, line: &str) {
+        if *bytes + line.len() > MAX_OUTPUT_BYTES {
+            *truncated = true;
+            return;
+        }
+        *bytes += line.len() + 1;
+        lines.push(line.to_string());
+    }
+
+    /// Join the captured lines, dropping the blank line the wrapper adds so
+    /// output without a trailing newline still ends before the next marker
+    fn join(mut lines: Vec<String>) -> String {
+        if lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        lines.join("\n")
+    }
+
+    fn finish(self) -> BatchOutput {
+        BatchOutput {
+            exit_code: self.exit_code,
+            stdout: Self::join(self.stdout),
+            stderr: Self::join(self.stderr),
+            truncated: self.truncated,
+            preamble: self.preamble.join("\n"),
+        }
+    }
+}
+
+/// Run the wrapped command on one target (blocking)
+fn exec_on_target(
+    profile: &str,
+    region: &str,
+    target: ExecTarget,
+    command: &str,
+    idle_timeout: Duration,
+) -> BatchExecTargetResult {
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let mut parser = BatchOutputParser::new();
+
+    let spawn_error = match ExecProcess::spawn(profile, region, &target, command, idle_timeout) {
+        Ok(mut process) => {
+            while let Some(line) = process.next_line() {
+                if !parser.push_line(&line) {
+                    break;
+                }
+            }
+            None
+        }
+        Err(e) => Some(e),
+    };
+
+    let output = parser.finish();
+    let (status, error) = match (output.exit_code, spawn_error) {
+        (_, Some(e)) => (BatchTargetStatus::Error, Some(e)),
+        (Some(0), None) => (BatchTargetStatus::Succeeded, None),
+        (Some(_), None) => (BatchTargetStatus::Failed, None),
+        (None, None) => {
+            let message = if output.preamble.is_empty() {
+                "Exec session ended without an exit status".to_string()
+            } else {
+                output.preamble.clone()
+            };
+            (BatchTargetStatus::Error, Some(message))
+        }
+    };
+
+    BatchExecTargetResult {
+        target,
+        status,
+        exit_code: output.exit_code,
+        stdout: output.stdout,
+        stderr: output.stderr,
+        truncated: output.truncated,
+        error,
+        started_at,
+        finished_at: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// Run a command on every target with at most `concurrency` exec sessions at once.
+/// Each target result is also emitted on `ecs:batch:result:{batch_id}` as it completes.
+pub async fn run_batch_exec(
+    app: AppHandle,
+    profile: String,
+    region: String,
+    batch_id: String,
+    input: BatchExecInput,
+) -> Result<BatchExecReport, String> {
+    if input.command.trim().is_empty() {
+        return Err("Command is empty".to_string());
+    }
+    if input.targets.is_empty() {
+        return Err("No targets selected".to_string());
+    }
+
+    let started_at = chrono::Utc::now().timestamp_millis();
+    let concurrency = input
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+    let idle_timeout = Duration::from_secs(
+        input
+            .idle_timeout_secs
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECS)
+            .max(5),
+    );
+    let command = Arc::new(wrap_command(&input.command));
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let event_name = format!("ecs:batch:result:{}", batch_id);
+
+    let mut handles = Vec::with_capacity(input.targets.len());
+    for target in input.targets {
+        let semaphore = semaphore.clone();
+        let command = command.clone();
+        let profile = profile.clone();
+        let region = region.clone();
+        let app = app.clone();
+        let event_name = event_name.clone();
+
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .map_err(|e| format!("Failed to schedule exec: {}", e))?;
+            let result = tokio::task::spawn_blocking(move || {
+                exec_on_target(&profile, &region, target, &command, idle_timeout)
+            })
+            .await
+            .map_err(|e| format!("Exec task failed: {}", e))?;
+
+            if let Err(e) = app.emit(&event_name, &result) {
+                tracing::error!("Failed to emit batch exec result: {}", e);
+            }
+            Ok::<_, String>(result)
+        }));
+    }
+
+    // Results keep the order of the input targets
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let result = handle
+            .await
+            .map_err(|e| format!("Exec task failed: {}", e))??;
+        results.push(result);
+    }
+
+    let count = |status: BatchTargetStatus| results.iter().filter(|r| r.status == status).count();
+
+    Ok(BatchExecReport {
+        batch_id,
+        command: input.command,
+        succeeded: count(BatchTargetStatus::Succeeded),
+        failed: count(BatchTargetStatus::Failed),
+        errors: count(BatchTargetStatus::Error),
+        results,
+        started_at,
+        finished_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> BatchOutput {
+        let mut parser = BatchOutputParser::new();
+        for line in lines {
+            if !parser.push_line(line) {
+                break;
+            }
+        }
+        parser.finish()
+    }
+
+    #[test]
+    fn test_parser_splits_streams() {
+        let output = parse(&[
+            "Starting session with SessionId: ecs-execute-command-123",
+            BEGIN_MARKER,
+            "MemTotal: 1 kB",
+            "",
+            STDERR_MARKER,
+            "warning",
+            "",
+            "__AWSC_EXIT__ 3",
+            "Exiting session with sessionId: ecs-execute-command-123.",
+        ]);
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(output.stdout, "MemTotal: 1 kB");
+        assert_eq!(output.stderr, "warning");
+        assert!(!output.truncated);
+    }
+
+    #[test]
+    fn test_parser_without_exit_status() {
+        let output = parse(&["An error occurred (TargetNotConnectedException)"]);
+        assert_eq!(output.exit_code, None);
+        assert_eq!(
+            output.preamble,
+            "An error occurred (TargetNotConnectedException)"
+        );
+    }
+
+    #[test]
+    fn test_parser_keeps_missing_trailing_newline() {
+        let output = parse(&[BEGIN_MARKER, "no newline", STDERR_MARKER, "__AWSC_EXIT__ 0"]);
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout, "no newline");
+        assert_eq!(output.stderr, "");
+    }
+}
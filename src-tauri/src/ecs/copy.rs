@@ -0,0 +1,454 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use super::exec::{validate_remote_path, ExecProcess, ExecTarget};
+
+const BEGIN_MARKER: &str = "__AWSC_BEGIN__";
+const END_MARKER: &str = "__AWSC_END__";
+const READY_MARKER: &str = "__AWSC_READY__";
+const EOF_MARKER: &str = "__AWSC_EOF__";
+const DONE_MARKER: &str = "__AWSC_DONE__";
+const ERROR_MARKER: &str = "__AWSC_ERROR__";
+
+/// Abort a transfer when the session produces no output for this long
+const IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+/// Minimum time between progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+/// Raw bytes per uploaded line (encodes to 76 base64 characters)
+const UPLOAD_CHUNK: usize = 57;
+
+/// Input for copying a file between a container and the local machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerCopyInput {
+    pub target: ExecTarget,
+    pub remote_path: String,
+    pub local_path: String,
+    /// Copy a directory (or file) as a .tar.gz archive, download only
+    #[serde(default)]
+    pub archive: bool,
+    /// ID used in `ecs:copy:progress` events, generated when not provided
+    pub transfer_id: Option<String>,
+}
+
+/// Direction of a container file transfer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyDirection {
+    FromContainer,
+    ToContainer,
+}
+
+/// Progress of a container file transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyProgress {
+    pub transfer_id: String,
+    pub direction: CopyDirection,
+    pub remote_path: String,
+    pub local_path: String,
+    pub bytes_transferred: u64,
+    pub total_bytes: Option<u64>,
+}
+
+/// Result of a completed container file transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyResult {
+    pub transfer_id: String,
+    pub direction: CopyDirection,
+    pub remote_path: String,
+    pub local_path: String,
+    pub bytes: u64,
+    pub sha256: String,
+    /// False when the container has no sha256sum and only the size was compared
+    pub checksum_verified: bool,
+}
+
+/// Emits throttled progress events for a transfer
+struct ProgressReporter {
+    app: AppHandle,
+    progress: CopyProgress,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressReporter {
+    fn new(
+        app: AppHandle,
+        transfer_id: &str,
+        direction: CopyDirection,
+        input: &ContainerCopyInput,
+    ) -> Self {
+        Self {
+            app,
+            progress: CopyProgress {
+                transfer_id: transfer_id.to_string(),
+                direction,
+                remote_path: input.remote_path.clone(),
+                local_path: input.local_path.clone(),
+                bytes_transferred: 0,
+                total_bytes: None,
+            },
+            last_emit: None,
+        }
+    }
+
+    fn update(&mut self, bytes_transferred: u64, force: bool) {
+        self.progress.bytes_transferred = bytes_transferred;
+        if !force
+            && self
+                .last_emit
+                .is_some_and(|t| t.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        self.last_emit = Some(Instant::now());
+        if let Err(e) = self.app.emit("ecs:copy:progress", &self.progress) {
+            tracing::error!("Failed to emit copy progress: {}", e);
+        }
+    }
+}
+
+/// Parse `<marker> <size> [sha256]` lines printed by the remote scripts
+fn parse_marker_line(line: &str, marker: &str) -> Option<(u64, Option<String>)> {
+    let rest = line.trim().strip_prefix(marker)?;
+    let mut parts = rest.split_whitespace();
+    let size = parts.next()?.parse().ok()?;
+    let sha = parts
+        .next()
+        .filter(|s| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(|s| s.to_lowercase());
+    Some((size, sha))
+}
+
+/// Remote script that prints the file (or a tarball of it) as base64 between markers
+fn download_script(remote_path: &str, archive: bool) -> String {
+    let emit = format!(
+        "echo \"{begin} $(wc -c < \"$f\")\"; base64 \"$f\"; echo \"{end} $(wc -c < \"$f\") $(sha256sum \"$f\" 2>/dev/null | cut -d \" \" -f 1)\"",
+        begin = BEGIN_MARKER,
+        end = END_MARKER,
+    );
+
+    if archive {
+        format!(
+            "p=\"{path}\"; f=/tmp/.awsc-$$.tar.gz; tar -czf \"$f\" -C \"$(dirname \"$p\")\" \"$(basename \"$p\")\" || {{ echo \"{err} tar failed\"; exit 1; }}; {emit}; rm -f \"$f\"",
+            path = remote_path,
+            err = ERROR_MARKER,
+            emit = emit,
+        )
+    } else {
+        format!(
+            "f=\"{path}\"; [ -f \"$f\" ] && [ -r \"$f\" ] || {{ echo \"{err} cannot read $f\"; exit 1; }}; {emit}",
+            path = remote_path,
+            err = ERROR_MARKER,
+            emit = emit,
+        )
+    }
+}
+
+/// Remote script that reads base64 lines until the EOF marker and writes the decoded file
+fn upload_script(remote_path: &str) -> String {
+    format!(
+        "f=\"{path}\"; t=\"$f.awsc-part\"; stty -echo 2>/dev/null; echo {ready}; \
+         while IFS= read -r l; do [ \"$l\" = {eof} ] && break; echo \"$l\"; done | base64 -d > \"$t\" \
+         && mv \"$t\" \"$f\" && echo \"{done} $(wc -c < \"$f\") $(sha256sum \"$f\" 2>/dev/null | cut -d \" \" -f 1)\" \
+         || {{ rm -f \"$t\"; echo \"{err} cannot write $f\"; }}",
+        path = remote_path,
+        ready = READY_MARKER,
+        eof = EOF_MARKER,
+        done = DONE_MARKER,
+        err = ERROR_MARKER,
+    )
+}
+
+/// Temporary file a download is written to before being renamed into place. Appended to
+/// the full file name so `dump.sql` and `dump.tar` never share one.
+fn part_path(local_path: &Path) -> PathBuf {
+    let mut file_name = local_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".awsc-part");
+    local_path.with_file_name(file_name)
+}
+
+/// Fill `buf` from `reader`, coming up short only at the end of the file. Every uploaded
+/// line but the last has to be a full chunk, padding mid-stream would corrupt the decode.
+fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Compare the remote size/checksum with what we computed locally
+fn verify_transfer(
+    local_size: u64,
+    local_sha: &str,
+    remote_size: u64,
+    remote_sha: Option<&str>,
+) -> Result<bool, String> {
+    if local_size != remote_size {
+        return Err(format!(
+            "Size mismatch: local {} bytes, remote {} bytes",
+            local_size, remote_size
+        ));
+    }
+    match remote_sha {
+        Some(sha) if sha != local_sha => Err(format!(
+            "Checksum mismatch: local {}, remote {}",
+            local_sha, sha
+        )),
+        Some(_) => Ok(true),
+        None => Ok(false),
+    }
+}
+
+/// Copy a file (or an archive of a directory) out of a container (blocking)
+pub fn copy_from_container(
+    app: AppHandle,
+    profile: &str,
+    region: &str,
+    transfer_id: &str,
+    input: &ContainerCopyInput,
+) -> Result<CopyResult, String> {
+    validate_remote_path(&input.remote_path)?;
+
+    let command = format!(
+        "sh -c '{}'",
+        download_script(&input.remote_path, input.archive)
+    );
+    let mut process = ExecProcess::spawn(profile, region, &input.target, &command, IDLE_TIMEOUT)?;
+    let mut reporter = ProgressReporter::new(app, transfer_id, CopyDirection::FromContainer, input);
+
+    // Wait for the start marker, anything before it is session manager chatter
+    loop {
+        let line = process
+            .next_line()
+            .ok_or_else(|| "Exec session ended before the transfer started".to_string())?;
+        if let Some(message) = line.trim().strip_prefix(ERROR_MARKER) {
+            return Err(message.trim().to_string());
+        }
+        if let Some(rest) = line.trim().strip_prefix(BEGIN_MARKER) {
+            reporter.progress.total_bytes = rest.trim().parse().ok();
+            break;
+        }
+    }
+
+    let local_path = Path::new(&input.local_path);
+    if let Some(parent) = local_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let part_path = part_path(local_path);
+    let mut file =
+        std::fs::File::create(&part_path).map_err(|e| format!("Failed to create file: {}", e))?;
+
+    let mut hasher = Sha256::new();
+    let mut written: u64 = 0;
+
+    let result = (|| {
+        let (remote_size, remote_sha) = loop {
+            let line = process
+                .next_line()
+                .ok_or_else(|| "Exec session ended before the transfer completed".to_string())?;
+
+            if let Some(end) = parse_marker_line(&line, END_MARKER) {
+                break end;
+            }
+
+            // base64 wraps at 76 characters, so every line decodes on its own
+            let chunk = BASE64
+                .decode(line.trim())
+                .map_err(|e| format!("Corrupt transfer data: {}", e))?;
+            hasher.update(&chunk);
+            file.write_all(&chunk)
+                .map_err(|e| format!("Failed to write file: {}", e))?;
+            written += chunk.len() as u64;
+            reporter.update(written, false);
+        };
+
+        file.flush()
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        let sha256 = format!("{:x}", hasher.finalize_reset());
+        let checksum_verified =
+            verify_transfer(written, &sha256, remote_size, remote_sha.as_deref())?;
+
+        Ok((sha256, checksum_verified))
+    })();
+
+    drop(file);
+    let (sha256, checksum_verified) = match result {
+        Ok(r) => r,
+        Err(e) => {
+            let _ = std::fs::remove_file(&part_path);
+            return Err(e);
+        }
+    };
+
+    std::fs::rename(&part_path, local_path).map_err(|e| format!("Failed to write file: {}", e))?;
+    reporter.update(written, true);
+
+    Ok(CopyResult {
+        transfer_id: transfer_id.to_string(),
+        direction: CopyDirection::FromContainer,
+        remote_path: input.remote_path.clone(),
+        local_path: input.local_path.clone(),
+        bytes: written,
+        sha256,
+        checksum_verified,
+    })
+}
+
+/// Copy a local file into a container (blocking)
+pub fn copy_to_container(
+    app: AppHandle,
+    profile: &str,
+    region: &str,
+    transfer_id: &str,
+    input: &ContainerCopyInput,
+) -> Result<CopyResult, String> {
+    validate_remote_path(&input.remote_path)?;
+    if input.archive {
+        return Err("Archive mode is only supported when copying from a container".to_string());
+    }
+
+    let file = std::fs::File::open(&input.local_path)
+        .map_err(|e| format!("Failed to read local file: {}", e))?;
+    let total_bytes = file
+        .metadata()
+        .map_err(|e| format!("Failed to read local file: {}", e))?
+        .len();
+    let mut reader = BufReader::new(file);
+
+    let command = format!("sh -c '{}'", upload_script(&input.remote_path));
+    let mut process = ExecProcess::spawn(profile, region, &input.target, &command, IDLE_TIMEOUT)?;
+    let mut reporter = ProgressReporter::new(app, transfer_id, CopyDirection::ToContainer, input);
+    reporter.progress.total_bytes = Some(total_bytes);
+
+    // Only send data once the remote side has disabled echo and is reading
+    loop {
+        let line = process
+            .next_line()
+            .ok_or_else(|| "Exec session ended before the transfer started".to_string())?;
+        if line.trim() == READY_MARKER {
+            break;
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    let mut sent: u64 = 0;
+    let mut chunk = [0u8; UPLOAD_CHUNK];
+    loop {
+        let len = read_chunk(&mut reader, &mut chunk)
+            .map_err(|e| format!("Failed to read local file: {}", e))?;
+        if len == 0 {
+            break;
+        }
+
+        hasher.update(&chunk[..len]);
+        let mut line = BASE64.encode(&chunk[..len]);
+        line.push('\n');
+        process.write(line.as_bytes())?;
+        sent += len as u64;
+        reporter.update(sent, false);
+
+        if len < UPLOAD_CHUNK {
+            break;
+        }
+    }
+    process.write(format!("{}\n", EOF_MARKER).as_bytes())?;
+
+    let (remote_size, remote_sha) = loop {
+        let line = process
+            .next_line()
+            .ok_or_else(|| "Exec session ended before the transfer was confirmed".to_string())?;
+        if let Some(message) = line.trim().strip_prefix(ERROR_MARKER) {
+            return Err(message.trim().to_string());
+        }
+        if let Some(done) = parse_marker_line(&line, DONE_MARKER) {
+            break done;
+        }
+    };
+
+    let sha256 = format!("{:x}", hasher.finalize());
+    let checksum_verified = verify_transfer(sent, &sha256, remote_size, remote_sha.as_deref())?;
+    reporter.update(sent, true);
+
+    Ok(CopyResult {
+        transfer_id: transfer_id.to_string(),
+        direction: CopyDirection::ToContainer,
+        remote_path: input.remote_path.clone(),
+        local_path: input.local_path.clone(),
+        bytes: sent,
+        sha256,
+        checksum_verified,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_marker_line() {
+        let sha = "a".repeat(64);
+        assert_eq!(
+            parse_marker_line(&format!("__AWSC_END__ 1024 {}", sha), END_MARKER),
+            Some((1024, Some(sha)))
+        );
+        // BusyBox wc pads the size, and sha256sum may be missing
+        assert_eq!(
+            parse_marker_line("__AWSC_DONE__    12 ", DONE_MARKER),
+            Some((12, None))
+        );
+        assert_eq!(
+            parse_marker_line("Starting session with SessionId", END_MARKER),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scripts_have_no_single_quotes() {
+        // Scripts are wrapped in sh -c '...'
+        assert!(!download_script("/tmp/x", false).contains('\''));
+        assert!(!download_script("/tmp/x", true).contains('\''));
+        assert!(!upload_script("/tmp/x").contains('\''));
+    }
+
+    #[test]
+    fn test_part_path_keeps_extension() {
+        assert_eq!(
+            part_path(Path::new("/tmp/dump.sql")),
+            Path::new("/tmp/dump.sql.awsc-part")
+        );
+        assert_ne!(
+            part_path(Path::new("/tmp/dump.sql")),
+            part_path(Path::new("/tmp/dump.tar"))
+        );
+    }
+
+    #[test]
+    fn test_read_chunk_fills_across_short_reads() {
+        let mut reader = std::io::Cursor::new(b"abc".to_vec()).chain(&b"defgh"[..]);
+        let mut buf = [0u8; 5];
+        assert_eq!(read_chunk(&mut reader, &mut buf).unwrap(), 5);
+        assert_eq!(&buf, b"abcde");
+        assert_eq!(read_chunk(&mut reader, &mut buf).unwrap(), 3);
+        assert_eq!(&buf[..3], b"fgh");
+        assert_eq!(read_chunk(&mut reader, &mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_verify_transfer() {
+        assert_eq!(verify_transfer(3, "abc", 3, Some("abc")), Ok(true));
+        assert_eq!(verify_transfer(3, "abc", 3, None), Ok(false));
+        assert!(verify_transfer(3, "abc", 4, Some("abc")).is_err());
+        assert!(verify_transfer(3, "abc", 3, Some("def")).is_err());
+    }
+}
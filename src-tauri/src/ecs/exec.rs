@@ -0,0 +1,258 @@
+use portable_pty::ChildKiller;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::terminal::session::PtySession;
+use crate::terminal::{create_pty_session, write_to_pty, SessionInfo, SessionStatus, SessionType};
+
+/// Container to run an exec session against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecTarget {
+    pub cluster: String,
+    pub task: String,
+    pub container: String,
+}
+
+/// Build the `aws ecs execute-command` arguments for a target
+pub fn ecs_exec_args(
+    cluster: &str,
+    task: &str,
+    container: &str,
+    command: &str,
+    profile: &str,
+    region: &str,
+) -> Vec<String> {
+    vec![
+        "ecs".to_string(),
+        "execute-command".to_string(),
+        "--cluster".to_string(),
+        cluster.to_string(),
+        "--task".to_string(),
+        task.to_string(),
+        "--container".to_string(),
+        container.to_string(),
+        // ECS Exec only supports interactive sessions
+        "--interactive".to_string(),
+        "--command".to_string(),
+        command.to_string(),
+        "--profile".to_string(),
+        profile.to_string(),
+        "--region".to_string(),
+        region.to_string(),
+    ]
+}
+
+/// Reject characters that would break out of the double-quoted strings in our scripts
+pub fn validate_remote_path(path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Err("Remote path is empty".to_string());
+    }
+    if let Some(c) = path
+        .chars()
+        .find(|c| matches!(c, '"' | '\'' | '$' | '`' | '\\' | '\n' | '\r'))
+    {
+        return Err(format!(
+            "Remote path contains unsupported character {:?}",
+            c
+        ));
+    }
+    Ok(())
+}
+
+/// Splits PTY output into lines, dropping the carriage returns added by the TTY
+#[derive(Default)]
+pub struct LineBuffer {
+    partial: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Feed raw output, returning every line completed by it
+    pub fn push(&mut self, data: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for &b in data {
+            match b {
+                b'\n' => {
+                    lines.push(String::from_utf8_lossy(&self.partial).into_owned());
+                    self.partial.clear();
+                }
+                b'\r' => {}
+                _ => self.partial.push(b),
+            }
+        }
+        lines
+    }
+
+    /// Return any trailing output not terminated by a newline
+    pub fn flush(&mut self) -> Option<String> {
+        if self.partial.is_empty() {
+            return None;
+        }
+        let line = String::from_utf8_lossy(&self.partial).into_owned();
+        self.partial.clear();
+        Some(line)
+    }
+}
+
+/// A non-interactive command running in a container through an exec session.
+/// Output is consumed line by line; the session is killed if it stays idle
+/// for longer than the idle timeout, or when the process is dropped.
+pub struct ExecProcess {
+    session: PtySession,
+    reader: Box<dyn Read + Send>,
+    lines: LineBuffer,
+    pending: VecDeque<String>,
+    eof: bool,
+    last_activity: Arc<AtomicI64>,
+    finished: Arc<AtomicBool>,
+}
+
+impl ExecProcess {
+    /// Start `command` in the target container
+    pub fn spawn(
+        profile: &str,
+        region: &str,
+        target: &ExecTarget,
+        command: &str,
+        idle_timeout: Duration,
+    ) -> Result<Self, String> {
+        let info = SessionInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: format!("Exec: {}", target.container),
+            session_type: SessionType::EcsExec {
+                cluster: target.cluster.clone(),
+                task: target.task.clone(),
+                container: target.container.clone(),
+                profile: profile.to_string(),
+                region: region.to_string(),
+            },
+            created_at: chrono::Utc::now().timestamp(),
+            status: SessionStatus::Running,
+        };
+
+        let args = ecs_exec_args(
+            &target.cluster,
+            &target.task,
+            &target.container,
+            command,
+            profile,
+            region,
+        );
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+
+        let mut session =
+            create_pty_session(info, "aws", &args_refs, vec![]).map_err(|e| e.to_string())?;
+        let reader = session
+            .reader
+            .take()
+            .ok_or_else(|| "PTY reader unavailable".to_string())?;
+
+        let last_activity = Arc::new(AtomicI64::new(chrono::Utc::now().timestamp_millis()));
+        let finished = Arc::new(AtomicBool::new(false));
+        spawn_idle_watchdog(
+            session.child.clone_killer(),
+            last_activity.clone(),
+            finished.clone(),
+            idle_timeout,
+        );
+
+        Ok(Self {
+            session,
+            reader,
+            lines: LineBuffer::default(),
+            pending: VecDeque::new(),
+            eof: false,
+            last_activity,
+            finished,
+        })
+    }
+
+    /// Read the next output line, or None once the session has ended
+    pub fn next_line(&mut self) -> Option<String> {
+        let mut buffer = [0u8; 8192];
+
+        loop {
+            if let Some(line) = self.pending.pop_front() {
+                return Some(line);
+            }
+            if self.eof {
+                return None;
+            }
+
+            match self.reader.read(&mut buffer) {
+                Ok(0) | Err(_) => {
+                    self.eof = true;
+                    self.pending.extend(self.lines.flush());
+                }
+                Ok(n) => {
+                    self.touch();
+                    self.pending.extend(self.lines.push(&buffer[..n]));
+                }
+            }
+        }
+    }
+
+    /// Send input to the remote command
+    pub fn write(&mut self, data: &[u8]) -> Result<(), String> {
+        self.touch();
+        write_to_pty(&mut self.session, data).map_err(|e| e.to_string())
+    }
+
+    fn touch(&self) {
+        self.last_activity
+            .store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+    }
+}
+
+impl Drop for ExecProcess {
+    fn drop(&mut self) {
+        self.finished.store(true, Ordering::SeqCst);
+        let _ = self.session.child.kill();
+    }
+}
+
+/// Kill the exec session when no output or input has been seen for `idle_timeout`
+fn spawn_idle_watchdog(
+    mut killer: Box<dyn ChildKiller + Send + Sync>,
+    last_activity: Arc<AtomicI64>,
+    finished: Arc<AtomicBool>,
+    idle_timeout: Duration,
+) {
+    std::thread::spawn(move || {
+        let idle_timeout_ms = idle_timeout.as_millis() as i64;
+        while !finished.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(500));
+            let idle = chrono::Utc::now().timestamp_millis() - last_activity.load(Ordering::SeqCst);
+            if idle > idle_timeout_ms {
+                tracing::warn!("Exec session idle for {}ms, killing it", idle);
+                let _ = killer.kill();
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_strips_carriage_returns() {
+        let mut buffer = LineBuffer::default();
+        assert_eq!(buffer.push(b"one\r\ntw"), vec!["one".to_string()]);
+        assert_eq!(buffer.push(b"o\r\nthree"), vec!["two".to_string()]);
+        assert_eq!(buffer.flush(), Some("three".to_string()));
+        assert_eq!(buffer.flush(), None);
+    }
+
+    #[test]
+    fn test_validate_remote_path() {
+        assert!(validate_remote_path("/tmp/heap dump.hprof").is_ok());
+        assert!(validate_remote_path("").is_err());
+        assert!(validate_remote_path("/tmp/$(reboot)").is_err());
+        assert!(validate_remote_path("/tmp/a\"b").is_err());
+    }
+}
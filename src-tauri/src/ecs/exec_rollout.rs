@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::aws::ecs::{describe_service, list_tasks, EcsTask};
+use crate::util::poll::{Polled, Poller};
+
+/// Phase of an enable-execute-command rollout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecRolloutPhase {
+    Deploying,
+    Completed,
+    Failed,
+    TimedOut,
+}
+
+/// Progress of replacing a service's tasks with exec-capable ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecRolloutProgress {
+    pub id: String,
+    pub cluster: String,
+    pub service: String,
+    pub phase: ExecRolloutPhase,
+    pub desired_count: i32,
+    pub running_count: i32,
+    /// Running tasks with execute command enabled and the exec agent running
+    pub exec_ready_count: i32,
+    /// Running tasks started before execute command was enabled
+    pub legacy_task_count: i32,
+    pub rollout_state: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Whether a task can be exec'd into right now
+fn task_exec_ready(task: &EcsTask) -> bool {
+    task.enable_execute_command
+        && task.last_status == "RUNNING"
+        && task
+            .containers
+            .iter()
+            .any(|c| c.exec_agent_status.as_deref() == Some("RUNNING"))
+}
+
+/// Spawn a background task that waits until the service only runs exec-capable tasks
+pub fn watch_exec_rollout(
+    app: AppHandle,
+    id: String,
+    profile: String,
+    region: String,
+    cluster: String,
+    service: String,
+) {
+    tokio::spawn(async move {
+        run_exec_rollout_watch(app, id, profile, region, cluster, service).await;
+    });
+}
+
+async fn run_exec_rollout_watch(
+    app: AppHandle,
+    id: String,
+    profile: String,
+    region: String,
+    cluster: String,
+    service: String,
+) {
+    let poller = Poller::new(Duration::from_secs(10), Duration::from_secs(20 * 60));
+
+    let progress_event = format!("ecs:exec-rollout:progress:{}", id);
+    let done_event = format!("ecs:exec-rollout:done:{}", id);
+
+    let mut last_progress: Option<ExecRolloutProgress> = None;
+
+    loop {
+        let state = poller
+            .poll(async {
+                let svc = describe_service(&profile, &region, &cluster, &service).await?;
+                let tasks = list_tasks(&profile, &region, &cluster, Some(&service)).await?;
+                Ok::<_, String>((svc, tasks))
+            })
+            .await;
+
+        let (svc, tasks) = match state {
+            Polled::Ready(state) => state,
+            Polled::Failed(e) => {
+                tracing::warn!("Exec rollout poll failed for {}: {}", service, e);
+                let _ = app.emit(&format!("ecs:exec-rollout:error:{}", id), &e);
+                continue;
+            }
+            Polled::TimedOut(e) => {
+                // Report the counts from the last successful poll along with the error
+                let mut progress = last_progress.unwrap_or(ExecRolloutProgress {
+                    id: id.clone(),
+                    cluster: cluster.clone(),
+                    service: service.clone(),
+                    phase: ExecRolloutPhase::Deploying,
+                    desired_count: 0,
+                    running_count: 0,
+                    exec_ready_count: 0,
+                    legacy_task_count: 0,
+                    rollout_state: None,
+                    message: None,
+                });
+                progress.phase = ExecRolloutPhase::TimedOut;
+                progress.message = Some(e);
+                let _ = app.emit(&done_event, &progress);
+                break;
+            }
+        };
+
+        let running: Vec<&EcsTask> = tasks
+            .iter()
+            .filter(|t| t.last_status == "RUNNING")
+            .collect();
+        let exec_ready_count = running.iter().filter(|t| task_exec_ready(t)).count() as i32;
+        let legacy_task_count = running.iter().filter(|t| !t.enable_execute_command).count() as i32;
+
+        let mut progress = ExecRolloutProgress {
+            id: id.clone(),
+            cluster: cluster.clone(),
+            service: service.clone(),
+            phase: ExecRolloutPhase::Deploying,
+            desired_count: svc.desired_count,
+            running_count: svc.running_count,
+            exec_ready_count,
+            legacy_task_count,
+            rollout_state: svc.rollout_state.clone(),
+            message: None,
+        };
+
+        if svc.rollout_state.as_deref() == Some("FAILED") {
+            progress.phase = ExecRolloutPhase::Failed;
+            progress.message = Some("Deployment failed".to_string());
+        } else if legacy_task_count == 0 && exec_ready_count >= svc.desired_count {
+            progress.phase = ExecRolloutPhase::Completed;
+        } else if poller.expired() {
+            progress.phase = ExecRolloutPhase::TimedOut;
+            progress.message = Some(format!(
+                "{} of {} tasks support exec after {} minutes",
+                exec_ready_count,
+                svc.desired_count,
+                poller.timeout().as_secs() / 60
+            ));
+        }
+
+        if progress.phase == ExecRolloutPhase::Deploying {
+            if let Err(e) = app.emit(&progress_event, &progress) {
+                tracing::error!("Failed to emit rollout progress: {}", e);
+            }
+            last_progress = Some(progress);
+        } else {
+            let _ = app.emit(&done_event, &progress);
+            break;
+        }
+    }
+}
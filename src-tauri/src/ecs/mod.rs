@@ -0,0 +1,7 @@
+pub mod batch;
+pub mod copy;
+pub mod exec;
+pub mod exec_rollout;
+pub mod preflight;
+pub mod run;
+pub mod watcher;
@@ -0,0 +1,439 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aws::cli::{aws_cli_version, ssm_plugin_version};
+use crate::aws::ecs::{
+    get_cluster_exec_config, get_task_exec_details, ClusterExecConfig, TaskExecDetails,
+};
+use crate::aws::iam::simulate_principal_actions;
+
+/// Actions the task role needs for the SSM agent to open exec channels
+const SSM_MESSAGES_ACTIONS: &[&str] = &[
+    "ssmmessages:CreateControlChannel",
+    "ssmmessages:CreateDataChannel",
+    "ssmmessages:OpenControlChannel",
+    "ssmmessages:OpenDataChannel",
+];
+
+/// Outcome of a single preflight check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Warn,
+    Skipped,
+}
+
+/// A single ECS Exec readiness check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightCheck {
+    pub id: String,
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub remediation: Option<String>,
+}
+
+impl PreflightCheck {
+    fn new(id: &str, name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            status,
+            message: message.into(),
+            remediation: None,
+        }
+    }
+
+    fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+}
+
+/// Structured ECS Exec readiness report for a task/container
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecPreflightReport {
+    pub task_arn: String,
+    pub container: String,
+    pub ready: bool,
+    pub checks: Vec<PreflightCheck>,
+}
+
+/// Run all ECS Exec readiness checks for a task/container
+pub async fn run_exec_preflight(
+    profile: &str,
+    region: &str,
+    cluster: &str,
+    task_arn: &str,
+    container: &str,
+) -> Result<ExecPreflightReport, String> {
+    let details = get_task_exec_details(profile, region, cluster, task_arn, container).await?;
+
+    let mut checks = vec![
+        check_task_running(&details),
+        check_execute_command_enabled(&details),
+        check_platform_version(&details),
+        check_exec_agent(&details),
+    ];
+
+    let cluster_config = match get_cluster_exec_config(profile, region, cluster).await {
+        Ok(config) => {
+            checks.push(check_cluster_config(&config));
+            Some(config)
+        }
+        Err(e) => {
+            checks.push(PreflightCheck::new(
+                "cluster_exec_config",
+                "Cluster exec configuration",
+                CheckStatus::Warn,
+                e,
+            ));
+            None
+        }
+    };
+
+    checks.extend(check_task_role(profile, region, &details, cluster_config.as_ref()).await);
+    let (aws_cli, ssm_plugin) = tokio::join!(aws_cli_version(), ssm_plugin_version());
+    checks.push(check_local_tool(
+        "aws_cli",
+        "AWS CLI",
+        aws_cli,
+        "Install the AWS CLI v2",
+    ));
+    checks.push(check_local_tool(
+        "session_manager_plugin",
+        "Session Manager plugin",
+        ssm_plugin,
+        "Install it from: https://docs.aws.amazon.com/systems-manager/latest/userguide/session-manager-working-with-install-plugin.html",
+    ));
+
+    Ok(ExecPreflightReport {
+        task_arn: details.task_arn,
+        container: container.to_string(),
+        ready: !checks.iter().any(|c| c.status == CheckStatus::Fail),
+        checks,
+    })
+}
+
+fn check_task_running(details: &TaskExecDetails) -> PreflightCheck {
+    const NAME: &str = "Task and container running";
+
+    if details.last_status != "RUNNING" {
+        return PreflightCheck::new(
+            "task_running",
+            NAME,
+            CheckStatus::Fail,
+            format!("Task is {}", details.last_status),
+        );
+    }
+
+    match details.container_status.as_deref() {
+        Some("RUNNING") => PreflightCheck::new(
+            "task_running",
+            NAME,
+            CheckStatus::Pass,
+            "Task and container are running",
+        ),
+        Some(status) => PreflightCheck::new(
+            "task_running",
+            NAME,
+            CheckStatus::Fail,
+            format!("Container is {}", status),
+        ),
+        None => PreflightCheck::new(
+            "task_running",
+            NAME,
+            CheckStatus::Fail,
+            "Container not found in task",
+        ),
+    }
+}
+
+fn check_execute_command_enabled(details: &TaskExecDetails) -> PreflightCheck {
+    const NAME: &str = "Execute command enabled";
+
+    if details.enable_execute_command {
+        PreflightCheck::new(
+            "enable_execute_command",
+            NAME,
+            CheckStatus::Pass,
+            "enableExecuteCommand is true",
+        )
+    } else {
+        PreflightCheck::new(
+            "enable_execute_command",
+            NAME,
+            CheckStatus::Fail,
+            "The task was started without enableExecuteCommand",
+        )
+        .with_remediation(
+            "Enable execute command on the service and force a new deployment so tasks are replaced",
+        )
+    }
+}
+
+fn check_platform_version(details: &TaskExecDetails) -> PreflightCheck {
+    const NAME: &str = "Fargate platform version";
+
+    if details.launch_type.as_deref() != Some("FARGATE") {
+        return PreflightCheck::new(
+            "platform_version",
+            NAME,
+            CheckStatus::Skipped,
+            "Not a Fargate task",
+        );
+    }
+
+    match details.platform_version.as_deref() {
+        Some(version) if platform_version_supports_exec(version) => PreflightCheck::new(
+            "platform_version",
+            NAME,
+            CheckStatus::Pass,
+            format!("Platform version {}", version),
+        ),
+        Some(version) => PreflightCheck::new(
+            "platform_version",
+            NAME,
+            CheckStatus::Fail,
+            format!("Platform version {} does not support ECS Exec", version),
+        )
+        .with_remediation("Use platform version 1.4.0 or later"),
+        None => PreflightCheck::new(
+            "platform_version",
+            NAME,
+            CheckStatus::Warn,
+            "Platform version is unknown",
+        ),
+    }
+}
+
+/// ECS Exec requires Fargate platform version 1.4.0 or later
+fn platform_version_supports_exec(version: &str) -> bool {
+    let parts: Vec<u32> = version
+        .trim_start_matches('v')
+        .split('.')
+        .filter_map(|p| p.parse().ok())
+        .collect();
+
+    match parts.as_slice() {
+        [major, minor, ..] => (*major, *minor) >= (1, 4),
+        [major] => *major > 1,
+        // Non-numeric versions such as LATEST resolve to a supported version
+        [] => true,
+    }
+}
+
+fn check_exec_agent(details: &TaskExecDetails) -> PreflightCheck {
+    const NAME: &str = "ExecuteCommandAgent";
+
+    match details.exec_agent_status.as_deref() {
+        Some("RUNNING") => PreflightCheck::new(
+            "exec_agent",
+            NAME,
+            CheckStatus::Pass,
+            "Managed agent is running",
+        ),
+        Some(status) => {
+            let message = match &details.exec_agent_reason {
+                Some(reason) => format!("Managed agent is {}: {}", status, reason),
+                None => format!("Managed agent is {}", status),
+            };
+            PreflightCheck::new("exec_agent", NAME, CheckStatus::Fail, message).with_remediation(
+                "Check the task role permissions and that the container can reach the SSM endpoints",
+            )
+        }
+        None => PreflightCheck::new(
+            "exec_agent",
+            NAME,
+            CheckStatus::Fail,
+            "Managed agent is not present in the container",
+        )
+        .with_remediation("Replace the task after enabling execute command"),
+    }
+}
+
+fn check_cluster_config(config: &ClusterExecConfig) -> PreflightCheck {
+    let mut parts = Vec::new();
+
+    if let Some(key) = &config.kms_key_id {
+        parts.push(format!("KMS key {}", key));
+    }
+    match config.logging.as_deref() {
+        Some("OVERRIDE") => {
+            if let Some(group) = &config.cloudwatch_log_group {
+                parts.push(format!("logging to CloudWatch group {}", group));
+            }
+            if let Some(bucket) = &config.s3_bucket {
+                parts.push(format!("logging to S3 bucket {}", bucket));
+            }
+        }
+        Some("NONE") => parts.push("session logging disabled".to_string()),
+        _ => parts.push("default logging".to_string()),
+    }
+
+    PreflightCheck::new(
+        "cluster_exec_config",
+        "Cluster exec configuration",
+        CheckStatus::Pass,
+        parts.join(", "),
+    )
+}
+
+/// Simulate the task role's policies for the actions ECS Exec needs
+async fn check_task_role(
+    profile: &str,
+    region: &str,
+    details: &TaskExecDetails,
+    cluster_config: Option<&ClusterExecConfig>,
+) -> Vec<PreflightCheck> {
+    let Some(role_arn) = details.task_role_arn.as_deref() else {
+        return vec![PreflightCheck::new(
+            "task_role_ssmmessages",
+            "Task role ssmmessages permissions",
+            CheckStatus::Fail,
+            "Task has no task role",
+        )
+        .with_remediation("Add a task role with ssmmessages permissions to the task definition")];
+    };
+
+    let mut checks = vec![
+        check_role_actions(
+            profile,
+            region,
+            role_arn,
+            "task_role_ssmmessages",
+            "Task role ssmmessages permissions",
+            SSM_MESSAGES_ACTIONS,
+            None,
+        )
+        .await,
+    ];
+
+    let Some(config) = cluster_config else {
+        return checks;
+    };
+
+    if let Some(key) = config.kms_key_id.as_deref() {
+        checks.push(
+            check_role_actions(
+                profile,
+                region,
+                role_arn,
+                "task_role_kms",
+                "Task role KMS permissions",
+                &["kms:Decrypt"],
+                key.starts_with("arn:").then_some(key),
+            )
+            .await,
+        );
+    }
+
+    if config.logging.as_deref() == Some("OVERRIDE") {
+        if config.cloudwatch_log_group.is_some() {
+            checks.push(
+                check_role_actions(
+                    profile,
+                    region,
+                    role_arn,
+                    "task_role_logs",
+                    "Task role CloudWatch Logs permissions",
+                    &[
+                        "logs:CreateLogStream",
+                        "logs:DescribeLogGroups",
+                        "logs:DescribeLogStreams",
+                        "logs:PutLogEvents",
+                    ],
+                    None,
+                )
+                .await,
+            );
+        }
+        if let Some(bucket) = config.s3_bucket.as_deref() {
+            let bucket_arn = format!("arn:aws:s3:::{}/*", bucket);
+            checks.push(
+                check_role_actions(
+                    profile,
+                    region,
+                    role_arn,
+                    "task_role_s3",
+                    "Task role S3 logging permissions",
+                    &["s3:PutObject"],
+                    Some(bucket_arn.as_str()),
+                )
+                .await,
+            );
+        }
+    }
+
+    checks
+}
+
+async fn check_role_actions(
+    profile: &str,
+    region: &str,
+    role_arn: &str,
+    id: &str,
+    name: &str,
+    actions: &[&str],
+    resource_arn: Option<&str>,
+) -> PreflightCheck {
+    match simulate_principal_actions(profile, region, role_arn, actions, resource_arn).await {
+        Ok(results) => {
+            let denied: Vec<&str> = results
+                .iter()
+                .filter(|r| !r.allowed)
+                .map(|r| r.action.as_str())
+                .collect();
+
+            if denied.is_empty() {
+                PreflightCheck::new(id, name, CheckStatus::Pass, "All required actions allowed")
+            } else {
+                PreflightCheck::new(
+                    id,
+                    name,
+                    CheckStatus::Fail,
+                    format!("Denied: {}", denied.join(", ")),
+                )
+                .with_remediation(format!(
+                    "Grant {} to {}",
+                    denied.join(", "),
+                    role_arn
+                ))
+            }
+        }
+        // Simulation needs iam:SimulatePrincipalPolicy, which many users don't have
+        Err(e) => PreflightCheck::new(
+            id,
+            name,
+            CheckStatus::Warn,
+            format!("Could not verify permissions: {}", e),
+        ),
+    }
+}
+
+fn check_local_tool(
+    id: &str,
+    name: &str,
+    version: Result<String, String>,
+    remediation: &str,
+) -> PreflightCheck {
+    match version {
+        Ok(version) => PreflightCheck::new(id, name, CheckStatus::Pass, version),
+        Err(e) => PreflightCheck::new(id, name, CheckStatus::Fail, e).with_remediation(remediation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_version_supports_exec() {
+        assert!(platform_version_supports_exec("1.4.0"));
+        assert!(platform_version_supports_exec("1.10.0"));
+        assert!(platform_version_supports_exec("2.0.0"));
+        assert!(!platform_version_supports_exec("1.3.0"));
+        assert!(platform_version_supports_exec("LATEST"));
+    }
+}
@@ -0,0 +1,341 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+use crate::aws::cloudwatch::{get_log_stream_events, LogEvent};
+use crate::aws::ecs::{describe_task, get_task_log_targets, ContainerLogTarget, EcsTask};
+
+/// Finished runs kept for the frontend, older ones are dropped
+const MAX_FINISHED_RUNS: usize = 50;
+
+/// Information about a one-off task run (serializable for frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsTaskRunInfo {
+    pub id: String,
+    pub cluster: String,
+    pub profile: String,
+    pub region: String,
+    pub status: EcsTaskRunStatus,
+    pub task: EcsTask,
+    pub created_at: i64,
+    pub finished_at: Option<i64>,
+}
+
+/// Status of a one-off task run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EcsTaskRunStatus {
+    Running,
+    Stopped,
+    Error,
+}
+
+/// Batch of log events from one container of a tracked task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EcsTaskRunLogs {
+    pub container: String,
+    pub events: Vec<LogEvent>,
+}
+
+/// Thread-safe registry of tracked one-off task runs
+pub struct EcsTaskRunRegistry {
+    runs: Mutex<HashMap<String, Arc<Mutex<EcsTaskRunInfo>>>>,
+}
+
+impl EcsTaskRunRegistry {
+    pub fn new() -> Self {
+        Self {
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a started task and begin tracking it until it stops
+    pub fn start_tracking(
+        &self,
+        app_handle: AppHandle,
+        id: String,
+        cluster: String,
+        profile: String,
+        region: String,
+        task: EcsTask,
+    ) -> EcsTaskRunInfo {
+        let info = EcsTaskRunInfo {
+            id: id.clone(),
+            cluster,
+            profile,
+            region,
+            status: EcsTaskRunStatus::Running,
+            task,
+            created_at: chrono::Utc::now().timestamp_millis(),
+            finished_at: None,
+        };
+
+        self.runs
+            .lock()
+            .insert(id.clone(), Arc::new(Mutex::new(info.clone())));
+
+        let tracked = info.clone();
+        tokio::spawn(async move {
+            run_task_tracker(app_handle, tracked).await;
+        });
+
+        info
+    }
+
+    /// Get a run by ID
+    pub fn get_run(&self, id: &str) -> Option<EcsTaskRunInfo> {
+        self.runs.lock().get(id).map(|r| r.lock().clone())
+    }
+
+    /// List all run infos
+    pub fn list_runs(&self) -> Vec<EcsTaskRunInfo> {
+        self.runs
+            .lock()
+            .values()
+            .map(|r| r.lock().clone())
+            .collect()
+    }
+
+    /// Update the tracked task state, returning the new info
+    fn update_run(&self, id: &str, task: EcsTask) -> Option<EcsTaskRunInfo> {
+        let runs = self.runs.lock();
+        let run = runs.get(id)?;
+        let mut info = run.lock();
+
+        if task.last_status == "STOPPED" && info.status == EcsTaskRunStatus::Running {
+            info.status = EcsTaskRunStatus::Stopped;
+            info.finished_at = Some(chrono::Utc::now().timestamp_millis());
+        }
+        info.task = task;
+
+        Some(info.clone())
+    }
+
+    /// Mark a run as failed (tracking could not continue)
+    fn mark_error(&self, id: &str) -> Option<EcsTaskRunInfo> {
+        let runs = self.runs.lock();
+        let run = runs.get(id)?;
+        let mut info = run.lock();
+
+        info.status = EcsTaskRunStatus::Error;
+        info.finished_at = Some(chrono::Utc::now().timestamp_millis());
+
+        Some(info.clone())
+    }
+
+    /// Drop the oldest finished runs beyond `MAX_FINISHED_RUNS`
+    fn prune_finished(&self) {
+        let mut runs = self.runs.lock();
+
+        let mut finished: Vec<(i64, String)> = runs
+            .values()
+            .filter_map(|r| {
+                let info = r.lock();
+                info.finished_at.map(|at| (at, info.id.clone()))
+            })
+            .collect();
+        if finished.len() <= MAX_FINISHED_RUNS {
+            return;
+        }
+
+        finished.sort_by_key(|(at, _)| std::cmp::Reverse(*at));
+        for (_, id) in finished.into_iter().skip(MAX_FINISHED_RUNS) {
+            runs.remove(&id);
+        }
+    }
+}
+
+impl Default for EcsTaskRunRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background task that polls the task state and streams container logs
+async fn run_task_tracker(app: AppHandle, run: EcsTaskRunInfo) {
+    let poll_interval = std::time::Duration::from_secs(5);
+    // awslogs delivery lags behind the task, keep reading for a few polls after it stops
+    const LOG_DRAIN_POLLS: u32 = 3;
+    // Give up after this many consecutive describe failures
+    const MAX_CONSECUTIVE_ERRORS: u32 = 10;
+
+    let log_targets = match get_task_log_targets(
+        &run.profile,
+        &run.region,
+        &run.task.task_definition_arn,
+        &run.task.arn,
+    )
+    .await
+    {
+        Ok(targets) => targets,
+        Err(e) => {
+            tracing::warn!("Cannot stream logs for task {}: {}", run.task.arn, e);
+            vec![]
+        }
+    };
+
+    let mut log_tokens: HashMap<String, String> = HashMap::new();
+    let mut last_status = run.task.last_status.clone();
+    let mut drain_polls_left: Option<u32> = None;
+    let mut consecutive_errors = 0;
+
+    loop {
+        if drain_polls_left.is_none() {
+            match describe_task(&run.profile, &run.region, &run.cluster, &run.task.arn).await {
+                Ok(task) => {
+                    consecutive_errors = 0;
+                    let stopped = task.last_status == "STOPPED";
+                    let status_changed = task.last_status != last_status;
+                    last_status = task.last_status.clone();
+
+                    if let Some(info) = TASK_RUN_REGISTRY.update_run(&run.id, task) {
+                        if status_changed {
+                            let event_name = format!("ecs:run:status:{}", run.id);
+                            if let Err(e) = app.emit(&event_name, &info) {
+                                tracing::error!("Failed to emit task status: {}", e);
+                            }
+                        }
+                    }
+
+                    if stopped {
+                        drain_polls_left = Some(LOG_DRAIN_POLLS);
+                    }
+                }
+                Err(e) => {
+                    consecutive_errors += 1;
+                    let event_name = format!("ecs:run:error:{}", run.id);
+                    let _ = app.emit(&event_name, &e);
+                    tracing::warn!("Task tracking error for {}: {}", run.id, e);
+
+                    if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                        TASK_RUN_REGISTRY.mark_error(&run.id);
+                        break;
+                    }
+                }
+            }
+        }
+
+        poll_task_logs(&app, &run, &log_targets, &mut log_tokens).await;
+
+        if let Some(left) = drain_polls_left.as_mut() {
+            if *left == 0 {
+                break;
+            }
+            *left -= 1;
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+
+    // Emit the final state, including container exit codes
+    let event_name = format!("ecs:run:stopped:{}", run.id);
+    if let Some(info) = TASK_RUN_REGISTRY.get_run(&run.id) {
+        let _ = app.emit(&event_name, &info);
+    }
+    TASK_RUN_REGISTRY.prune_finished();
+}
+
+/// Fetch new log events for each container and emit them to the frontend
+async fn poll_task_logs(
+    app: &AppHandle,
+    run: &EcsTaskRunInfo,
+    targets: &[ContainerLogTarget],
+    tokens: &mut HashMap<String, String>,
+) {
+    for target in targets {
+        let region = target.region.as_deref().unwrap_or(&run.region);
+
+        // The stream only exists once the container has started, so errors are expected early on
+        let Ok((events, next_token)) = get_log_stream_events(
+            &run.profile,
+            region,
+            &target.log_group,
+            &target.log_stream,
+            None,
+            None,
+            tokens.get(&target.container).map(|s| s.as_str()),
+        )
+        .await
+        else {
+            continue;
+        };
+
+        if let Some(token) = next_token {
+            tokens.insert(target.container.clone(), token);
+        }
+
+        if !events.is_empty() {
+            let event_name = format!("ecs:run:logs:{}", run.id);
+            let batch = EcsTaskRunLogs {
+                container: target.container.clone(),
+                events,
+            };
+            if let Err(e) = app.emit(&event_name, &batch) {
+                tracing::error!("Failed to emit task logs: {}", e);
+            }
+        }
+    }
+}
+
+// Global registry instance
+use once_cell::sync::Lazy;
+
+pub static TASK_RUN_REGISTRY: Lazy<EcsTaskRunRegistry> = Lazy::new(EcsTaskRunRegistry::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, finished_at: Option<i64>) -> EcsTaskRunInfo {
+        EcsTaskRunInfo {
+            id: id.to_string(),
+            cluster: "cluster".to_string(),
+            profile: "default".to_string(),
+            region: "us-east-1".to_string(),
+            status: if finished_at.is_some() {
+                EcsTaskRunStatus::Stopped
+            } else {
+                EcsTaskRunStatus::Running
+            },
+            task: EcsTask {
+                arn: format!("arn:aws:ecs:us-east-1:123456789012:task/cluster/{}", id),
+                task_definition_arn: String::new(),
+                cluster_arn: String::new(),
+                last_status: "STOPPED".to_string(),
+                desired_status: "STOPPED".to_string(),
+                launch_type: None,
+                containers: vec![],
+                enable_execute_command: false,
+                stopped_reason: None,
+                group: None,
+                started_by: None,
+                started_at: None,
+            },
+            created_at: 0,
+            finished_at,
+        }
+    }
+
+    #[test]
+    fn test_prune_finished_keeps_running_and_newest() {
+        let registry = EcsTaskRunRegistry::new();
+        {
+            let mut runs = registry.runs.lock();
+            runs.insert("running".into(), Arc::new(Mutex::new(run("running", None))));
+            for i in 0..MAX_FINISHED_RUNS + 5 {
+                let id = format!("run-{}", i);
+                runs.insert(id.clone(), Arc::new(Mutex::new(run(&id, Some(i as i64)))));
+            }
+        }
+
+        registry.prune_finished();
+
+        let runs = registry.list_runs();
+        assert_eq!(runs.len(), MAX_FINISHED_RUNS + 1);
+        assert!(registry.get_run("running").is_some());
+        assert!(registry.get_run("run-4").is_none());
+        assert!(registry.get_run("run-5").is_some());
+    }
+}
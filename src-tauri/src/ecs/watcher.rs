@@ -0,0 +1,435 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::oneshot;
+
+use crate::aws::ecs::{
+    describe_service_snapshots, EcsDeployment, EcsServiceEvent, EcsServiceSnapshot,
+};
+
+/// Information about a service watch session (serializable for frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceWatchInfo {
+    pub id: String,
+    pub cluster: String,
+    pub services: Vec<String>,
+    pub profile: String,
+    pub region: String,
+    /// Alert when a service stays below its desired count for this long
+    pub alert_after_minutes: Option<u32>,
+    pub poll_interval_secs: u64,
+    pub status: ServiceWatchStatus,
+    pub created_at: i64,
+}
+
+/// Status of a service watch session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceWatchStatus {
+    Running,
+    Stopped,
+}
+
+/// Change detected between two polls of a service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ServiceWatchEvent {
+    /// New entries in the service event log (oldest first)
+    ServiceEvents {
+        service: String,
+        events: Vec<EcsServiceEvent>,
+    },
+    /// A deployment appeared or its status/rollout state changed
+    DeploymentChanged {
+        service: String,
+        deployment: EcsDeployment,
+        previous_status: Option<String>,
+        previous_rollout_state: Option<String>,
+    },
+    /// Running, pending or desired count changed
+    CountDrift {
+        service: String,
+        running_count: i32,
+        pending_count: i32,
+        desired_count: i32,
+    },
+    /// Service has been below its desired count for longer than the threshold
+    BelowDesiredAlert {
+        service: String,
+        running_count: i32,
+        desired_count: i32,
+        below_since: i64,
+    },
+    /// Service is back at its desired count after an alert
+    Recovered {
+        service: String,
+        running_count: i32,
+        desired_count: i32,
+        below_since: i64,
+    },
+}
+
+/// What we remember about a service between polls
+#[derive(Debug, Default)]
+struct ServiceWatchState {
+    initialized: bool,
+    seen_event_ids: HashSet<String>,
+    deployments: HashMap<String, (String, Option<String>)>,
+    counts: (i32, i32, i32),
+    below_since: Option<i64>,
+    alerted: bool,
+}
+
+impl ServiceWatchState {
+    /// Compare a new snapshot with the previous one and return the changes.
+    /// The first observation only records a baseline.
+    fn observe(
+        &mut self,
+        snapshot: &EcsServiceSnapshot,
+        now: i64,
+        alert_after_ms: Option<i64>,
+    ) -> Vec<ServiceWatchEvent> {
+        let service = snapshot.service.name.clone();
+        let running = snapshot.service.running_count;
+        let desired = snapshot.service.desired_count;
+        let pending = snapshot.pending_count;
+        let mut changes = Vec::new();
+
+        let new_events: Vec<EcsServiceEvent> = snapshot
+            .events
+            .iter()
+            .filter(|e| !self.seen_event_ids.contains(&e.id))
+            .rev()
+            .cloned()
+            .collect();
+        // ECS only returns the latest 100 events, so the current IDs are all we need to keep
+        self.seen_event_ids = snapshot.events.iter().map(|e| e.id.clone()).collect();
+
+        if self.initialized && !new_events.is_empty() {
+            changes.push(ServiceWatchEvent::ServiceEvents {
+                service: service.clone(),
+                events: new_events,
+            });
+        }
+
+        let mut deployments = HashMap::new();
+        for deployment in &snapshot.deployments {
+            let current = (deployment.status.clone(), deployment.rollout_state.clone());
+            let previous = self.deployments.get(&deployment.id);
+
+            if self.initialized && previous != Some(&current) {
+                changes.push(ServiceWatchEvent::DeploymentChanged {
+                    service: service.clone(),
+                    deployment: deployment.clone(),
+                    previous_status: previous.map(|p| p.0.clone()),
+                    previous_rollout_state: previous.and_then(|p| p.1.clone()),
+                });
+            }
+            deployments.insert(deployment.id.clone(), current);
+        }
+        self.deployments = deployments;
+
+        if self.initialized && self.counts != (running, pending, desired) {
+            changes.push(ServiceWatchEvent::CountDrift {
+                service: service.clone(),
+                running_count: running,
+                pending_count: pending,
+                desired_count: desired,
+            });
+        }
+        self.counts = (running, pending, desired);
+
+        if running < desired {
+            let below_since = *self.below_since.get_or_insert(now);
+            if let Some(threshold) = alert_after_ms {
+                if !self.alerted && now - below_since >= threshold {
+                    self.alerted = true;
+                    changes.push(ServiceWatchEvent::BelowDesiredAlert {
+                        service: service.clone(),
+                        running_count: running,
+                        desired_count: desired,
+                        below_since,
+                    });
+                }
+            }
+        } else if let Some(below_since) = self.below_since.take() {
+            if self.alerted {
+                self.alerted = false;
+                changes.push(ServiceWatchEvent::Recovered {
+                    service,
+                    running_count: running,
+                    desired_count: desired,
+                    below_since,
+                });
+            }
+        }
+
+        self.initialized = true;
+        changes
+    }
+}
+
+/// Internal service watch session state
+pub struct ServiceWatchSession {
+    pub info: ServiceWatchInfo,
+    pub stop_signal: Arc<AtomicBool>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl ServiceWatchSession {
+    pub fn stop(&mut self) {
+        self.stop_signal.store(true, Ordering::SeqCst);
+        self.info.status = ServiceWatchStatus::Stopped;
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// Thread-safe registry of all active service watch sessions
+pub struct ServiceWatchRegistry {
+    sessions: Mutex<HashMap<String, Arc<Mutex<ServiceWatchSession>>>>,
+}
+
+impl ServiceWatchRegistry {
+    pub fn new() -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create and start a new service watch session
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_session(
+        &self,
+        app_handle: AppHandle,
+        id: String,
+        cluster: String,
+        services: Vec<String>,
+        profile: String,
+        region: String,
+        alert_after_minutes: Option<u32>,
+        poll_interval_secs: u64,
+    ) -> ServiceWatchInfo {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let stop_signal = Arc::new(AtomicBool::new(false));
+
+        let info = ServiceWatchInfo {
+            id: id.clone(),
+            cluster,
+            services,
+            profile,
+            region,
+            alert_after_minutes,
+            poll_interval_secs,
+            status: ServiceWatchStatus::Running,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        };
+
+        let session = ServiceWatchSession {
+            info: info.clone(),
+            stop_signal: stop_signal.clone(),
+            shutdown_tx: Some(shutdown_tx),
+        };
+
+        self.sessions
+            .lock()
+            .insert(id, Arc::new(Mutex::new(session)));
+
+        let watch = info.clone();
+        tokio::spawn(async move {
+            run_service_watch(app_handle, watch, stop_signal, shutdown_rx).await;
+        });
+
+        info
+    }
+
+    /// Stop and remove a session
+    pub fn stop_session(&self, id: &str) -> bool {
+        if let Some(session) = self.sessions.lock().remove(id) {
+            session.lock().stop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// List all session infos
+    pub fn list_sessions(&self) -> Vec<ServiceWatchInfo> {
+        self.sessions
+            .lock()
+            .values()
+            .map(|s| s.lock().info.clone())
+            .collect()
+    }
+}
+
+impl Default for ServiceWatchRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background task that polls describe_services and emits detected changes
+async fn run_service_watch(
+    app: AppHandle,
+    watch: ServiceWatchInfo,
+    stop_signal: Arc<AtomicBool>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    let poll_interval = std::time::Duration::from_secs(watch.poll_interval_secs);
+    let alert_after_ms = watch.alert_after_minutes.map(|m| i64::from(m) * 60 * 1000);
+    let mut states: HashMap<String, ServiceWatchState> = HashMap::new();
+
+    loop {
+        if stop_signal.load(Ordering::SeqCst) {
+            break;
+        }
+
+        match describe_service_snapshots(
+            &watch.profile,
+            &watch.region,
+            &watch.cluster,
+            &watch.services,
+        )
+        .await
+        {
+            Ok(snapshots) => {
+                let now = chrono::Utc::now().timestamp_millis();
+                let event_name = format!("ecs:watch:event:{}", watch.id);
+
+                for snapshot in &snapshots {
+                    let state = states.entry(snapshot.service.arn.clone()).or_default();
+                    for change in state.observe(snapshot, now, alert_after_ms) {
+                        if let Err(e) = app.emit(&event_name, &change) {
+                            tracing::error!("Failed to emit service watch event: {}", e);
+                        }
+                    }
+                }
+
+                let event_name = format!("ecs:watch:snapshot:{}", watch.id);
+                let _ = app.emit(&event_name, &snapshots);
+            }
+            Err(e) => {
+                let event_name = format!("ecs:watch:error:{}", watch.id);
+                if let Err(emit_err) = app.emit(&event_name, &e) {
+                    tracing::error!("Failed to emit service watch error: {}", emit_err);
+                }
+
+                // Continue polling despite errors (might be temporary)
+                tracing::warn!("Service watch error for {}: {}", watch.id, e);
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(poll_interval) => {},
+            _ = &mut shutdown_rx => {
+                break;
+            }
+        }
+    }
+
+    let event_name = format!("ecs:watch:stopped:{}", watch.id);
+    let _ = app.emit(&event_name, ());
+}
+
+// Global registry instance
+use once_cell::sync::Lazy;
+
+pub static SERVICE_WATCH_REGISTRY: Lazy<ServiceWatchRegistry> =
+    Lazy::new(ServiceWatchRegistry::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::ecs::EcsService;
+
+    fn snapshot(running: i32, desired: i32, event_ids: &[&str]) -> EcsServiceSnapshot {
+        EcsServiceSnapshot {
+            service: EcsService {
+                arn: "arn:aws:ecs:us-east-1:123456789012:service/prod/api".to_string(),
+                name: "api".to_string(),
+                cluster_arn: "arn:aws:ecs:us-east-1:123456789012:cluster/prod".to_string(),
+                status: "ACTIVE".to_string(),
+                desired_count: desired,
+                running_count: running,
+                launch_type: Some("FARGATE".to_string()),
+                enable_execute_command: true,
+                rollout_state: Some("COMPLETED".to_string()),
+                load_balancers: vec![],
+                service_registries: vec![],
+                service_connect: None,
+            },
+            pending_count: 0,
+            events: event_ids
+                .iter()
+                .map(|id| EcsServiceEvent {
+                    id: id.to_string(),
+                    created_at: None,
+                    message: format!("event {}", id),
+                })
+                .collect(),
+            deployments: vec![],
+        }
+    }
+
+    #[test]
+    fn test_first_observation_is_baseline() {
+        let mut state = ServiceWatchState::default();
+        let changes = state.observe(&snapshot(2, 2, &["b", "a"]), 0, Some(60_000));
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_new_events_are_emitted_oldest_first() {
+        let mut state = ServiceWatchState::default();
+        state.observe(&snapshot(2, 2, &["a"]), 0, None);
+
+        let changes = state.observe(&snapshot(2, 2, &["c", "b", "a"]), 1_000, None);
+        match changes.as_slice() {
+            [ServiceWatchEvent::ServiceEvents { events, .. }] => {
+                let ids: Vec<&str> = events.iter().map(|e| e.id.as_str()).collect();
+                assert_eq!(ids, vec!["b", "c"]);
+            }
+            other => panic!("unexpected changes: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_below_desired_alert_and_recovery() {
+        let mut state = ServiceWatchState::default();
+        state.observe(&snapshot(2, 2, &[]), 0, Some(60_000));
+
+        // Drops below desired: drift only, no alert yet
+        let changes = state.observe(&snapshot(1, 2, &[]), 10_000, Some(60_000));
+        assert!(matches!(
+            changes.as_slice(),
+            [ServiceWatchEvent::CountDrift { .. }]
+        ));
+
+        // Still below after the threshold: alert once
+        let changes = state.observe(&snapshot(1, 2, &[]), 70_000, Some(60_000));
+        assert!(matches!(
+            changes.as_slice(),
+            [ServiceWatchEvent::BelowDesiredAlert {
+                below_since: 10_000,
+                ..
+            }]
+        ));
+        let changes = state.observe(&snapshot(1, 2, &[]), 80_000, Some(60_000));
+        assert!(changes.is_empty());
+
+        // Back at desired count
+        let changes = state.observe(&snapshot(2, 2, &[]), 90_000, Some(60_000));
+        assert!(matches!(
+            changes.as_slice(),
+            [
+                ServiceWatchEvent::CountDrift { .. },
+                ServiceWatchEvent::Recovered { .. }
+            ]
+        ));
+    }
+}
@@ -1,7 +1,11 @@
 mod aws;
 mod commands;
+mod ec2;
+mod ecs;
 mod logs;
 mod terminal;
+mod tunnels;
+mod util;
 
 use commands::{
     // AWS profile commands
@@ -18,10 +22,54 @@ use commands::{
     validate_credentials,
     // Resource discovery commands
     discover_resources,
+    list_ec2_instance_groups,
     list_ec2_instances,
     list_ecs_clusters,
     list_ecs_services,
     list_ecs_tasks,
+    // EC2 instance commands
+    change_ec2_instance_state,
+    clear_ssm_command_history,
+    describe_ec2_instance_detail,
+    describe_ssm_session_document,
+    get_ec2_console_output,
+    get_ec2_console_screenshot,
+    get_ssm_command_invocations,
+    list_auto_scaling_groups,
+    list_ssm_command_history,
+    list_ssm_session_documents,
+    list_ssm_sessions,
+    rerun_ssm_command,
+    send_ssm_command,
+    set_asg_desired_capacity,
+    set_asg_instance_standby,
+    start_asg_instance_refresh,
+    terminate_ssm_session,
+    write_ssh_config_entry,
+    // Database commands
+    list_elasticache_clusters,
+    list_rds_databases,
+    suggest_bastion_instances,
+    // ECS task commands
+    ecs_batch_exec,
+    ecs_copy_from_container,
+    ecs_copy_to_container,
+    ecs_exec_preflight,
+    enable_ecs_service_exec,
+    get_ecs_cluster_metrics,
+    get_ecs_service_endpoint,
+    get_ecs_service_metrics,
+    get_ecs_service_network_config,
+    get_ecs_task_endpoint,
+    get_ecs_task_run,
+    list_ecs_capacity_providers,
+    list_ecs_container_instances,
+    list_ecs_service_watches,
+    list_ecs_task_runs,
+    run_ecs_task,
+    start_ecs_service_watch,
+    stop_ecs_service_watch,
+    stop_ecs_task_run,
     // Terminal commands
     terminal_close,
     terminal_create_session,
@@ -29,6 +77,15 @@ use commands::{
     terminal_list_sessions,
     terminal_resize,
     terminal_write,
+    // Tunnel commands
+    check_local_port,
+    delete_saved_tunnel,
+    get_tunnel_connection_info,
+    list_saved_tunnels,
+    list_tunnel_states,
+    save_tunnel,
+    start_tunnel,
+    stop_tunnel,
     // CloudWatch Logs commands
     get_cloudwatch_log_events,
     list_cloudwatch_log_groups,
@@ -52,6 +109,14 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            // Bring up saved tunnels marked to start at launch
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                tunnels::TUNNEL_MANAGER.start_auto_start_tunnels(app_handle);
+            });
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // AWS profile commands
             list_aws_profiles,
@@ -71,6 +136,50 @@ pub fn run() {
             list_ecs_services,
             list_ecs_tasks,
             list_ec2_instances,
+            list_ec2_instance_groups,
+            // EC2 instance commands
+            change_ec2_instance_state,
+            describe_ec2_instance_detail,
+            get_ec2_console_output,
+            get_ec2_console_screenshot,
+            send_ssm_command,
+            rerun_ssm_command,
+            get_ssm_command_invocations,
+            list_ssm_command_history,
+            clear_ssm_command_history,
+            list_ssm_session_documents,
+            describe_ssm_session_document,
+            list_ssm_sessions,
+            terminate_ssm_session,
+            write_ssh_config_entry,
+            list_auto_scaling_groups,
+            set_asg_desired_capacity,
+            set_asg_instance_standby,
+            start_asg_instance_refresh,
+            // Database commands
+            list_rds_databases,
+            list_elasticache_clusters,
+            suggest_bastion_instances,
+            // ECS task commands
+            run_ecs_task,
+            stop_ecs_task_run,
+            get_ecs_task_run,
+            list_ecs_task_runs,
+            get_ecs_service_network_config,
+            ecs_exec_preflight,
+            enable_ecs_service_exec,
+            get_ecs_cluster_metrics,
+            get_ecs_service_metrics,
+            list_ecs_container_instances,
+            list_ecs_capacity_providers,
+            start_ecs_service_watch,
+            stop_ecs_service_watch,
+            list_ecs_service_watches,
+            ecs_copy_from_container,
+            ecs_copy_to_container,
+            ecs_batch_exec,
+            get_ecs_task_endpoint,
+            get_ecs_service_endpoint,
             // Terminal commands
             terminal_create_session,
             terminal_write,
@@ -78,6 +187,15 @@ pub fn run() {
             terminal_close,
             terminal_list_sessions,
             terminal_get_session,
+            // Tunnel commands
+            list_saved_tunnels,
+            save_tunnel,
+            delete_saved_tunnel,
+            start_tunnel,
+            stop_tunnel,
+            list_tunnel_states,
+            check_local_port,
+            get_tunnel_connection_info,
             // CloudWatch Logs commands
             list_cloudwatch_log_groups,
             list_cloudwatch_log_streams,
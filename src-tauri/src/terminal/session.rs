@@ -1,7 +1,7 @@
 use parking_lot::Mutex;
 use portable_pty::Child;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Write};
 use std::sync::Arc;
 
@@ -30,6 +30,9 @@ pub enum SessionType {
         instance_id: String,
         profile: String,
         region: String,
+        /// Start a command or custom Session document instead of the default shell
+        #[serde(default)]
+        document: Option<SessionDocument>,
     },
     SsmPortForwarding {
         instance_id: String,
@@ -39,9 +42,139 @@ pub enum SessionType {
         profile: String,
         region: String,
     },
+    /// Real SSH through an SSM `AWS-StartSSHSession` proxy, signing in with a temporary
+    /// key pushed by EC2 Instance Connect
+    InstanceConnectSsh {
+        instance_id: String,
+        os_user: String,
+        profile: String,
+        region: String,
+    },
+    /// Port forwarding through the SSM agent of an ECS container (ECS Exec must be enabled)
+    EcsPortForwarding {
+        cluster: String,
+        task: String,
+        container: String,
+        runtime_id: String,
+        local_port: u16,
+        remote_port: u16,
+        remote_host: Option<String>,
+        profile: String,
+        region: String,
+    },
+    /// Database client connected to the local end of a port forward
+    DatabaseClient {
+        connection: ClientConnection,
+        /// Sign in with an RDS IAM authentication token instead of a password
+        iam_auth: Option<IamAuthTarget>,
+    },
     Local,
 }
 
+/// Session Manager document to start instead of the default shell
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionDocument {
+    /// Run a command with a terminal, e.g. `sudo -u app bash` or a database console
+    InteractiveCommand { command: String },
+    /// Run a command without a terminal and stream its output
+    NonInteractiveCommand { command: String },
+    /// Session document owned by or shared with the account
+    Custom {
+        name: String,
+        #[serde(default)]
+        parameters: BTreeMap<String, Vec<String>>,
+    },
+}
+
+impl SessionDocument {
+    pub fn document_name(&self) -> &str {
+        match self {
+            Self::InteractiveCommand { .. } => "AWS-StartInteractiveCommand",
+            Self::NonInteractiveCommand { .. } => "AWS-StartNonInteractiveCommand",
+            Self::Custom { name, .. } => name,
+        }
+    }
+
+    /// Document parameters as the JSON `--parameters` accepts. JSON rather than the
+    /// shorthand syntax so commands may contain commas and quotes.
+    pub fn parameters_json(&self) -> Option<String> {
+        let parameters = match self {
+            Self::InteractiveCommand { command } | Self::NonInteractiveCommand { command } => {
+                BTreeMap::from([("command".to_string(), vec![command.clone()])])
+            }
+            Self::Custom { parameters, .. } if parameters.is_empty() => return None,
+            Self::Custom { parameters, .. } => parameters.clone(),
+        };
+        serde_json::to_string(&parameters).ok()
+    }
+
+    /// Short label for session titles
+    pub fn label(&self) -> &str {
+        match self {
+            Self::InteractiveCommand { command } | Self::NonInteractiveCommand { command } => {
+                command
+            }
+            Self::Custom { name, .. } => name,
+        }
+    }
+}
+
+/// Command-line database client to connect through a tunnel with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DbClient {
+    Psql,
+    Mysql,
+    RedisCli,
+}
+
+impl DbClient {
+    /// Pick the client for an RDS or ElastiCache engine name
+    pub fn from_engine(engine: &str) -> Option<Self> {
+        let engine = engine.to_lowercase();
+        if engine.contains("postgres") {
+            Some(Self::Psql)
+        } else if engine.contains("mysql") || engine.contains("mariadb") {
+            Some(Self::Mysql)
+        } else if engine.contains("redis") || engine.contains("valkey") {
+            Some(Self::RedisCli)
+        } else {
+            None
+        }
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Psql => "psql",
+            Self::Mysql => "mysql",
+            Self::RedisCli => "redis-cli",
+        }
+    }
+}
+
+/// Where a client should connect on the local end of a tunnel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientConnection {
+    pub client: DbClient,
+    pub local_port: u16,
+    pub username: Option<String>,
+    pub database: Option<String>,
+    /// Require TLS. Certificates are issued for the real endpoint, not localhost,
+    /// so encryption is required without verifying the host name.
+    #[serde(default)]
+    pub tls: bool,
+}
+
+/// Real RDS endpoint an IAM authentication token is signed for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IamAuthTarget {
+    pub profile: String,
+    pub region: String,
+    pub hostname: String,
+    pub port: u16,
+}
+
 /// Status of a terminal session
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -110,3 +243,20 @@ impl Default for SessionRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_engine() {
+        assert_eq!(
+            DbClient::from_engine("aurora-postgresql"),
+            Some(DbClient::Psql)
+        );
+        assert_eq!(DbClient::from_engine("aurora-mysql"), Some(DbClient::Mysql));
+        assert_eq!(DbClient::from_engine("mariadb"), Some(DbClient::Mysql));
+        assert_eq!(DbClient::from_engine("valkey"), Some(DbClient::RedisCli));
+        assert_eq!(DbClient::from_engine("sqlserver-ex"), None);
+    }
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+
+use crate::aws::rds::{generate_iam_auth_token, IAM_AUTH_TOKEN_TTL_SECS};
+use crate::terminal::session::{ClientConnection, DbClient, IamAuthTarget};
+use crate::util::shell::shell_quote;
+
+/// Ready-to-use ways of connecting to a tunnelled database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionInfo {
+    pub client: DbClient,
+    /// Connection URI, without the password
+    pub uri: String,
+    /// Shell command running the client, including the auth token when one was minted
+    pub command_line: String,
+    pub auth_token: Option<String>,
+    pub auth_token_expires_at: Option<i64>,
+}
+
+/// Program, arguments and environment to run a client
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+}
+
+impl ClientCommand {
+    /// Render as a single shell command line, with the environment inline
+    pub fn to_command_line(&self) -> String {
+        self.env
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, shell_quote(v)))
+            .chain(std::iter::once(self.program.clone()))
+            .chain(self.args.iter().map(|a| shell_quote(a)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Build the client invocation. The password goes through the environment so it
+/// stays out of the process list.
+pub fn client_command(conn: &ClientConnection, password: Option<&str>) -> ClientCommand {
+    let port = conn.local_port.to_string();
+    let mut args = Vec::new();
+    let mut env = Vec::new();
+
+    match conn.client {
+        DbClient::Psql => {
+            let mut conninfo = format!("host=localhost port={}", port);
+            if let Some(user) = &conn.username {
+                conninfo.push_str(&format!(" user={}", conninfo_quote(user)));
+            }
+            if let Some(database) = &conn.database {
+                conninfo.push_str(&format!(" dbname={}", conninfo_quote(database)));
+            }
+            if conn.tls {
+                conninfo.push_str(" sslmode=require");
+            }
+            args.push(conninfo);
+            if let Some(password) = password {
+                env.push(("PGPASSWORD".to_string(), password.to_string()));
+            }
+        }
+        DbClient::Mysql => {
+            // "localhost" would make mysql use the unix socket
+            args.extend(["-h", "127.0.0.1", "-P", &port].map(String::from));
+            if let Some(user) = &conn.username {
+                args.extend(["-u".to_string(), user.clone()]);
+            }
+            if conn.tls {
+                args.push("--ssl-mode=REQUIRED".to_string());
+            }
+            if let Some(password) = password {
+                // IAM tokens are sent as a cleartext password, which mysql only does when asked
+                args.push("--enable-cleartext-plugin".to_string());
+                env.push(("MYSQL_PWD".to_string(), password.to_string()));
+            }
+            if let Some(database) = &conn.database {
+                args.push(database.clone());
+            }
+        }
+        DbClient::RedisCli => {
+            args.extend(["-h", "localhost", "-p", &port].map(String::from));
+            if let Some(user) = &conn.username {
+                args.extend(["--user".to_string(), user.clone()]);
+            }
+            if conn.tls {
+                args.push("--tls".to_string());
+            }
+            if let Some(database) = &conn.database {
+                args.extend(["-n".to_string(), database.clone()]);
+            }
+            if let Some(password) = password {
+                env.push(("REDISCLI_AUTH".to_string(), password.to_string()));
+            }
+        }
+    }
+
+    ClientCommand {
+        program: conn.client.program().to_string(),
+        args,
+        env,
+    }
+}
+
+/// Quote a libpq conninfo value when it is empty or has spaces, quotes or backslashes
+fn conninfo_quote(value: &str) -> String {
+    if !value.is_empty() && !value.contains(|c: char| c.is_whitespace() || c == '\'' || c == '\\') {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Connection URI for tools that take one, without the password
+pub fn connection_uri(conn: &ClientConnection) -> String {
+    let user = conn
+        .username
+        .as_deref()
+        .map(|u| format!("{}@", u))
+        .unwrap_or_default();
+    let database = conn.database.as_deref().unwrap_or_default();
+
+    match conn.client {
+        DbClient::Psql => format!(
+            "postgresql://{}localhost:{}/{}{}",
+            user,
+            conn.local_port,
+            database,
+            if conn.tls { "?sslmode=require" } else { "" }
+        ),
+        DbClient::Mysql => format!(
+            "mysql://{}127.0.0.1:{}/{}{}",
+            user,
+            conn.local_port,
+            database,
+            if conn.tls { "?ssl-mode=REQUIRED" } else { "" }
+        ),
+        DbClient::RedisCli => format!(
+            "{}://{}localhost:{}/{}",
+            if conn.tls { "rediss" } else { "redis" },
+            user,
+            conn.local_port,
+            database
+        ),
+    }
+}
+
+/// Mint an RDS IAM authentication token for the connection's user, when requested.
+/// IAM authentication only works over TLS, so `conn.tls` is turned on as well.
+pub async fn iam_auth_token(
+    conn: &mut ClientConnection,
+    iam_auth: Option<&IamAuthTarget>,
+) -> Result<Option<String>, String> {
+    let Some(target) = iam_auth else {
+        return Ok(None);
+    };
+    let username = conn
+        .username
+        .as_deref()
+        .ok_or_else(|| "A database user is required for IAM authentication".to_string())?;
+
+    let token = generate_iam_auth_token(
+        &target.profile,
+        &target.region,
+        &target.hostname,
+        target.port,
+        username,
+    )
+    .await?;
+
+    conn.tls = true;
+    Ok(Some(token))
+}
+
+/// Build the connection URI and client command line for a tunnel
+pub async fn connection_info(
+    mut conn: ClientConnection,
+    iam_auth: Option<&IamAuthTarget>,
+) -> Result<ConnectionInfo, String> {
+    let auth_token = iam_auth_token(&mut conn, iam_auth).await?;
+    let auth_token_expires_at = auth_token
+        .as_ref()
+        .map(|_| chrono::Utc::now().timestamp_millis() + IAM_AUTH_TOKEN_TTL_SECS * 1000);
+
+    Ok(ConnectionInfo {
+        client: conn.client,
+        uri: connection_uri(&conn),
+        command_line: client_command(&conn, auth_token.as_deref()).to_command_line(),
+        auth_token,
+        auth_token_expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(client: DbClient, tls: bool) -> ClientConnection {
+        ClientConnection {
+            client,
+            local_port: 15432,
+            username: Some("app_user".to_string()),
+            database: Some("orders".to_string()),
+            tls,
+        }
+    }
+
+    #[test]
+    fn test_psql_command() {
+        let cmd = client_command(&connection(DbClient::Psql, true), Some("tok'en"));
+        assert_eq!(cmd.program, "psql");
+        assert_eq!(
+            cmd.args,
+            vec!["host=localhost port=15432 user=app_user dbname=orders sslmode=require"]
+        );
+        assert_eq!(
+            cmd.to_command_line(),
+            r"PGPASSWORD='tok'\''en' psql 'host=localhost port=15432 user=app_user dbname=orders sslmode=require'"
+        );
+    }
+
+    #[test]
+    fn test_psql_conninfo_quoting() {
+        let mut conn = connection(DbClient::Psql, false);
+        conn.username = Some("o'brien".to_string());
+        conn.database = Some(r"sales db\eu".to_string());
+        assert_eq!(
+            client_command(&conn, None).args,
+            vec![r"host=localhost port=15432 user='o\'brien' dbname='sales db\\eu'"]
+        );
+    }
+
+    #[test]
+    fn test_mysql_command_with_token() {
+        let cmd = client_command(&connection(DbClient::Mysql, true), Some("token"));
+        assert_eq!(
+            cmd.args,
+            vec![
+                "-h",
+                "127.0.0.1",
+                "-P",
+                "15432",
+                "-u",
+                "app_user",
+                "--ssl-mode=REQUIRED",
+                "--enable-cleartext-plugin",
+                "orders"
+            ]
+        );
+        assert_eq!(
+            cmd.env,
+            vec![("MYSQL_PWD".to_string(), "token".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_connection_uri() {
+        assert_eq!(
+            connection_uri(&connection(DbClient::Psql, true)),
+            "postgresql://app_user@localhost:15432/orders?sslmode=require"
+        );
+        let mut redis = connection(DbClient::RedisCli, true);
+        redis.username = None;
+        redis.database = None;
+        assert_eq!(connection_uri(&redis), "rediss://localhost:15432/");
+    }
+}
@@ -0,0 +1,511 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::oneshot;
+
+use super::ports::ensure_local_port;
+use super::store::{list_tunnels, TunnelDefinition, TunnelTarget};
+use crate::aws::ecs::{ecs_ssm_target, list_tasks};
+
+/// Give up after this many consecutive attempts that never connected
+const MAX_FAILURES: u32 = 8;
+const MAX_RECONNECT_DELAY_SECS: u64 = 60;
+/// Printed by the session manager plugin once the local port is listening
+const CONNECTED_MARKER: &str = "Waiting for connections";
+
+/// Build the `aws ssm start-session` arguments for port forwarding to an SSM target
+pub fn port_forwarding_args(
+    target: &str,
+    local_port: u16,
+    remote_port: u16,
+    remote_host: Option<&str>,
+    profile: &str,
+    region: &str,
+) -> Vec<String> {
+    let mut args = vec![
+        "ssm".to_string(),
+        "start-session".to_string(),
+        "--target".to_string(),
+        target.to_string(),
+    ];
+
+    // Choose document based on whether remote_host is specified
+    if let Some(host) = remote_host {
+        // Remote host forwarding (for RDS/ElastiCache endpoints)
+        args.push("--document-name".to_string());
+        args.push("AWS-StartPortForwardingSessionToRemoteHost".to_string());
+        args.push("--parameters".to_string());
+        args.push(format!(
+            "host={},portNumber={},localPortNumber={}",
+            host, remote_port, local_port
+        ));
+    } else {
+        // Local port forwarding to the target itself
+        args.push("--document-name".to_string());
+        args.push("AWS-StartPortForwardingSession".to_string());
+        args.push("--parameters".to_string());
+        args.push(format!(
+            "portNumber={},localPortNumber={}",
+            remote_port, local_port
+        ));
+    }
+
+    args.push("--profile".to_string());
+    args.push(profile.to_string());
+    args.push("--region".to_string());
+    args.push(region.to_string());
+
+    args
+}
+
+/// Status of a running tunnel
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TunnelStatus {
+    Starting,
+    Connected,
+    Reconnecting,
+    Stopped,
+    /// Gave up after repeated failures
+    Failed,
+}
+
+/// Live state of a tunnel (serializable for frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelState {
+    pub tunnel_id: String,
+    pub name: String,
+    pub status: TunnelStatus,
+    pub local_port: u16,
+    /// SSM target of the current connection
+    pub ssm_target: Option<String>,
+    /// Consecutive failed attempts
+    pub attempt: u32,
+    pub last_error: Option<String>,
+    pub connected_at: Option<i64>,
+    pub next_retry_at: Option<i64>,
+}
+
+/// Internal tunnel state
+struct ActiveTunnel {
+    state: TunnelState,
+    stop_signal: Arc<AtomicBool>,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+impl ActiveTunnel {
+    fn stop(&mut self) {
+        self.stop_signal.store(true, Ordering::SeqCst);
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        !matches!(
+            self.state.status,
+            TunnelStatus::Stopped | TunnelStatus::Failed
+        )
+    }
+}
+
+/// Thread-safe registry of running tunnels, keyed by saved tunnel ID
+pub struct TunnelManager {
+    tunnels: Mutex<HashMap<String, Arc<Mutex<ActiveTunnel>>>>,
+}
+
+impl TunnelManager {
+    pub fn new() -> Self {
+        Self {
+            tunnels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start a saved tunnel, or return its state if it is already running
+    pub fn start(&self, app_handle: AppHandle, tunnel: TunnelDefinition) -> TunnelState {
+        let mut tunnels = self.tunnels.lock();
+        if let Some(existing) = tunnels.get(&tunnel.id) {
+            let existing = existing.lock();
+            if existing.is_active() {
+                return existing.state.clone();
+            }
+        }
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let stop_signal = Arc::new(AtomicBool::new(false));
+
+        let state = TunnelState {
+            tunnel_id: tunnel.id.clone(),
+            name: tunnel.name.clone(),
+            status: TunnelStatus::Starting,
+            local_port: tunnel.local_port,
+            ssm_target: None,
+            attempt: 0,
+            last_error: None,
+            connected_at: None,
+            next_retry_at: None,
+        };
+
+        let entry = Arc::new(Mutex::new(ActiveTunnel {
+            state: state.clone(),
+            stop_signal: stop_signal.clone(),
+            shutdown_tx: Some(shutdown_tx),
+        }));
+        tunnels.insert(tunnel.id.clone(), entry.clone());
+
+        tokio::spawn(async move {
+            run_tunnel(app_handle, tunnel, entry, stop_signal, shutdown_rx).await;
+        });
+
+        state
+    }
+
+    /// Stop and remove a tunnel
+    pub fn stop(&self, tunnel_id: &str) -> bool {
+        if let Some(entry) = self.tunnels.lock().remove(tunnel_id) {
+            entry.lock().stop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// List the state of all started tunnels
+    pub fn list_states(&self) -> Vec<TunnelState> {
+        self.tunnels
+            .lock()
+            .values()
+            .map(|t| t.lock().state.clone())
+            .collect()
+    }
+
+    /// Start every saved tunnel marked to start at launch
+    pub fn start_auto_start_tunnels(&self, app_handle: AppHandle) {
+        match list_tunnels() {
+            Ok(tunnels) => {
+                for tunnel in tunnels.into_iter().filter(|t| t.auto_start) {
+                    tracing::info!("Starting tunnel {} at launch", tunnel.name);
+                    self.start(app_handle.clone(), tunnel);
+                }
+            }
+            Err(e) => tracing::error!("Failed to load saved tunnels: {}", e),
+        }
+    }
+}
+
+impl Default for TunnelManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub static TUNNEL_MANAGER: Lazy<TunnelManager> = Lazy::new(TunnelManager::new);
+
+/// Delay before reconnect attempt `attempt` (1-based): 1s, 2s, 4s, ... capped at 60s
+fn reconnect_delay(attempt: u32) -> Duration {
+    let secs = 1u64
+        .checked_shl(attempt.saturating_sub(1))
+        .unwrap_or(u64::MAX)
+        .min(MAX_RECONNECT_DELAY_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Update the tunnel state and emit it on `tunnel:status:{id}`
+fn update_state(app: &AppHandle, entry: &Mutex<ActiveTunnel>, f: impl FnOnce(&mut TunnelState)) {
+    let state = {
+        let mut entry = entry.lock();
+        f(&mut entry.state);
+        entry.state.clone()
+    };
+
+    let event_name = format!("tunnel:status:{}", state.tunnel_id);
+    if let Err(e) = app.emit(&event_name, &state) {
+        tracing::error!("Failed to emit tunnel status: {}", e);
+    }
+}
+
+/// Resolve the SSM target to connect through
+async fn resolve_ssm_target(tunnel: &TunnelDefinition) -> Result<String, String> {
+    match &tunnel.target {
+        TunnelTarget::Instance { instance_id } => Ok(instance_id.clone()),
+        TunnelTarget::EcsTask {
+            cluster,
+            task,
+            runtime_id,
+            ..
+        } => Ok(ecs_ssm_target(cluster, task, runtime_id)),
+        TunnelTarget::EcsService {
+            cluster,
+            service,
+            container,
+        } => {
+            let tasks = list_tasks(&tunnel.profile, &tunnel.region, cluster, Some(service)).await?;
+            tasks
+                .iter()
+                .filter(|t| t.last_status == "RUNNING")
+                .find_map(|t| {
+                    t.containers
+                        .iter()
+                        .find(|c| &c.name == container)
+                        .and_then(|c| c.runtime_id.as_deref())
+                        .map(|runtime_id| ecs_ssm_target(cluster, &t.arn, runtime_id))
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "No running task of {} has a {} container",
+                        service, container
+                    )
+                })
+        }
+    }
+}
+
+enum AttemptOutcome {
+    /// The tunnel was stopped
+    Shutdown,
+    /// The session ended on its own
+    Exited {
+        connected: bool,
+        error: Option<String>,
+    },
+}
+
+/// Background task that keeps a tunnel up, reconnecting with backoff
+async fn run_tunnel(
+    app: AppHandle,
+    tunnel: TunnelDefinition,
+    entry: Arc<Mutex<ActiveTunnel>>,
+    stop_signal: Arc<AtomicBool>,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) {
+    let mut failures: u32 = 0;
+
+    loop {
+        if stop_signal.load(Ordering::SeqCst) {
+            break;
+        }
+
+        match run_attempt(&app, &tunnel, &entry, &mut shutdown_rx).await {
+            AttemptOutcome::Shutdown => break,
+            AttemptOutcome::Exited { connected, error } => {
+                if connected {
+                    failures = 0;
+                }
+                failures += 1;
+
+                if failures > MAX_FAILURES {
+                    tracing::warn!(
+                        "Tunnel {} failed {} times, giving up",
+                        tunnel.name,
+                        failures
+                    );
+                    update_state(&app, &entry, |s| {
+                        s.status = TunnelStatus::Failed;
+                        s.attempt = failures;
+                        s.last_error = error;
+                        s.connected_at = None;
+                        s.next_retry_at = None;
+                    });
+                    return;
+                }
+
+                let delay = reconnect_delay(failures);
+                update_state(&app, &entry, |s| {
+                    s.status = TunnelStatus::Reconnecting;
+                    s.attempt = failures;
+                    s.last_error = error;
+                    s.connected_at = None;
+                    s.next_retry_at =
+                        Some(chrono::Utc::now().timestamp_millis() + delay.as_millis() as i64);
+                });
+
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+        }
+    }
+
+    update_state(&app, &entry, |s| {
+        s.status = TunnelStatus::Stopped;
+        s.connected_at = None;
+        s.next_retry_at = None;
+    });
+}
+
+/// Run a single port forwarding session until it exits or the tunnel is stopped
+async fn run_attempt(
+    app: &AppHandle,
+    tunnel: &TunnelDefinition,
+    entry: &Arc<Mutex<ActiveTunnel>>,
+    shutdown_rx: &mut oneshot::Receiver<()>,
+) -> AttemptOutcome {
+    // Saved tunnels keep their port, a conflict is retried like any other failure
+    let port = tunnel.local_port;
+    let port_check = tokio::task::spawn_blocking(move || ensure_local_port(port, false))
+        .await
+        .map_err(|e| format!("Failed to check local port: {}", e))
+        .and_then(|r| r);
+    if let Err(e) = port_check {
+        return AttemptOutcome::Exited {
+            connected: false,
+            error: Some(e),
+        };
+    }
+
+    let target = match resolve_ssm_target(tunnel).await {
+        Ok(target) => target,
+        Err(e) => {
+            return AttemptOutcome::Exited {
+                connected: false,
+                error: Some(e),
+            }
+        }
+    };
+
+    update_state(app, entry, |s| {
+        if s.status != TunnelStatus::Reconnecting {
+            s.status = TunnelStatus::Starting;
+        }
+        s.ssm_target = Some(target.clone());
+        s.next_retry_at = None;
+    });
+
+    let args = port_forwarding_args(
+        &target,
+        tunnel.local_port,
+        tunnel.remote_port,
+        tunnel.remote_host.as_deref(),
+        &tunnel.profile,
+        &tunnel.region,
+    );
+
+    let mut child = match Command::new("aws")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            return AttemptOutcome::Exited {
+                connected: false,
+                error: Some(format!("Failed to start aws CLI: {}", e)),
+            }
+        }
+    };
+
+    let connected = Arc::new(AtomicBool::new(false));
+    let last_line = Arc::new(Mutex::new(None));
+
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(watch_output(
+            stdout,
+            app.clone(),
+            entry.clone(),
+            connected.clone(),
+            last_line.clone(),
+        ));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(watch_output(
+            stderr,
+            app.clone(),
+            entry.clone(),
+            connected.clone(),
+            last_line.clone(),
+        ));
+    }
+
+    tokio::select! {
+        status = child.wait() => {
+            let error = last_line.lock().take().or_else(|| {
+                status.ok().map(|s| format!("Session exited with {}", s))
+            });
+            AttemptOutcome::Exited {
+                connected: connected.load(Ordering::SeqCst),
+                error,
+            }
+        }
+        _ = shutdown_rx => {
+            let _ = child.kill().await;
+            AttemptOutcome::Shutdown
+        }
+    }
+}
+
+/// Watch session output for the connected marker, keeping the last line for error reporting
+async fn watch_output<R: AsyncRead + Unpin>(
+    reader: R,
+    app: AppHandle,
+    entry: Arc<Mutex<ActiveTunnel>>,
+    connected: Arc<AtomicBool>,
+    last_line: Arc<Mutex<Option<String>>>,
+) {
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.contains(CONNECTED_MARKER) {
+            if !connected.swap(true, Ordering::SeqCst) {
+                update_state(&app, &entry, |s| {
+                    s.status = TunnelStatus::Connected;
+                    s.attempt = 0;
+                    s.last_error = None;
+                    s.connected_at = Some(chrono::Utc::now().timestamp_millis());
+                });
+            }
+        } else {
+            *last_line.lock() = Some(line.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay_backs_off_and_caps() {
+        assert_eq!(reconnect_delay(1), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(2), Duration::from_secs(2));
+        assert_eq!(reconnect_delay(4), Duration::from_secs(8));
+        assert_eq!(reconnect_delay(7), Duration::from_secs(60));
+        assert_eq!(reconnect_delay(100), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_port_forwarding_args_documents() {
+        let args = port_forwarding_args(
+            "i-0abc",
+            15432,
+            5432,
+            Some("db.internal"),
+            "dev",
+            "eu-west-1",
+        );
+        assert!(args.contains(&"AWS-StartPortForwardingSessionToRemoteHost".to_string()));
+        assert!(
+            args.contains(&"host=db.internal,portNumber=5432,localPortNumber=15432".to_string())
+        );
+
+        let args = port_forwarding_args("i-0abc", 8080, 80, None, "dev", "eu-west-1");
+        assert!(args.contains(&"AWS-StartPortForwardingSession".to_string()));
+        assert!(args.contains(&"portNumber=80,localPortNumber=8080".to_string()));
+    }
+}
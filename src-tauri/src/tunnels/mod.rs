@@ -0,0 +1,6 @@
+pub mod clients;
+pub mod manager;
+pub mod ports;
+pub mod store;
+
+pub use manager::{port_forwarding_args, TUNNEL_MANAGER};
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, Ipv6Addr, TcpListener};
+
+/// How many ports above the requested one to try when picking a free port
+const FREE_PORT_SEARCH_RANGE: u16 = 100;
+
+/// Local process listening on a port
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PortOwner {
+    pub pid: u32,
+    pub name: String,
+    pub command_line: Option<String>,
+}
+
+/// Availability of a local port
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalPortStatus {
+    pub port: u16,
+    pub available: bool,
+    /// Process holding the port, when it can be determined (Linux only)
+    pub owner: Option<PortOwner>,
+    /// Next free port above `port`, when it is taken
+    pub suggested_port: Option<u16>,
+}
+
+/// Whether a listener could be bound on the loopback interfaces for `port`.
+/// Only "address in use" counts as taken, so hosts without IPv6 still work.
+pub fn is_port_available(port: u16) -> bool {
+    let in_use = |result: std::io::Result<TcpListener>| matches!(result, Err(e) if e.kind() == ErrorKind::AddrInUse);
+
+    !in_use(TcpListener::bind((Ipv4Addr::LOCALHOST, port)))
+        && !in_use(TcpListener::bind((Ipv6Addr::LOCALHOST, port)))
+}
+
+/// Find the first free port after `port`
+pub fn find_free_port(port: u16) -> Option<u16> {
+    (1..=FREE_PORT_SEARCH_RANGE)
+        .filter_map(|offset| port.checked_add(offset))
+        .find(|&p| is_port_available(p))
+}
+
+/// Check a local port, including who holds it and a free alternative
+pub fn check_local_port(port: u16) -> LocalPortStatus {
+    if is_port_available(port) {
+        return LocalPortStatus {
+            port,
+            available: true,
+            owner: None,
+            suggested_port: None,
+        };
+    }
+
+    LocalPortStatus {
+        port,
+        available: false,
+        owner: find_port_owner(port),
+        suggested_port: find_free_port(port),
+    }
+}
+
+/// Make sure `port` can be used for a port forward, returning the port to use.
+/// With `pick_free_port` a taken port is replaced by the next free one.
+pub fn ensure_local_port(port: u16, pick_free_port: bool) -> Result<u16, String> {
+    let status = check_local_port(port);
+    if status.available {
+        return Ok(port);
+    }
+
+    if pick_free_port {
+        if let Some(free) = status.suggested_port {
+            tracing::info!("Local port {} is in use, using {} instead", port, free);
+            return Ok(free);
+        }
+    }
+
+    Err(match status.owner {
+        Some(owner) => format!(
+            "Local port {} is already in use by {} (pid {})",
+            port, owner.name, owner.pid
+        ),
+        None => format!("Local port {} is already in use", port),
+    })
+}
+
+/// Socket inodes listening on `port` in a /proc/net/tcp{,6} table
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn listening_inodes(table: &str, port: u16) -> Vec<u64> {
+    const TCP_LISTEN: &str = "0A";
+
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            if u16::from_str_radix(local_port, 16).ok()? != port || *fields.get(3)? != TCP_LISTEN {
+                return None;
+            }
+            fields.get(9)?.parse().ok()
+        })
+        .filter(|&inode| inode != 0)
+        .collect()
+}
+
+/// Find the process listening on a local port by matching socket inodes in /proc
+#[cfg(target_os = "linux")]
+pub fn find_port_owner(port: u16) -> Option<PortOwner> {
+    use std::fs;
+
+    let inodes: Vec<u64> = ["/proc/net/tcp", "/proc/net/tcp6"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|table| listening_inodes(&table, port))
+        .collect();
+    if inodes.is_empty() {
+        return None;
+    }
+
+    let targets: Vec<String> = inodes.iter().map(|i| format!("socket:[{}]", i)).collect();
+
+    for entry in fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        // Processes of other users are not readable, skip them
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        let owns_socket = fds.flatten().any(|fd| {
+            fs::read_link(fd.path())
+                .map(|link| targets.iter().any(|t| link.as_os_str() == t.as_str()))
+                .unwrap_or(false)
+        });
+
+        if owns_socket {
+            let name = fs::read_to_string(entry.path().join("comm"))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|_| pid.to_string());
+            let command_line = fs::read(entry.path().join("cmdline"))
+                .ok()
+                .map(|raw| {
+                    String::from_utf8_lossy(&raw)
+                        .split('\0')
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter(|s| !s.is_empty());
+
+            return Some(PortOwner {
+                pid,
+                name,
+                command_line,
+            });
+        }
+    }
+
+    None
+}
+
+/// Finding the owning process is only supported on Linux
+#[cfg(not(target_os = "linux"))]
+pub fn find_port_owner(_port: u16) -> Option<PortOwner> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TCP_TABLE: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 48213 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1538 0100007F:D2F0 01 00000000:00000000 00:00000000 00000000  1000        0 48999 1 0000000000000000 20 4 30 10 -1
+   2: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1234 1 0000000000000000 100 0 0 10 0";
+
+    #[test]
+    fn test_listening_inodes() {
+        // 0x1538 = 5432, the established connection on the same port is ignored
+        assert_eq!(listening_inodes(TCP_TABLE, 5432), vec![48213]);
+        assert_eq!(listening_inodes(TCP_TABLE, 22), vec![1234]);
+        assert!(listening_inodes(TCP_TABLE, 8080).is_empty());
+    }
+
+    #[test]
+    fn test_ensure_local_port_detects_conflict() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(!is_port_available(port));
+        assert!(ensure_local_port(port, false).is_err());
+
+        let picked = ensure_local_port(port, true).unwrap();
+        assert_ne!(picked, port);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_find_port_owner() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let owner = find_port_owner(port).expect("owner of our own listener");
+        assert_eq!(owner.pid, std::process::id());
+    }
+}
@@ -0,0 +1,181 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::util::fs::{app_config_path, write_atomic};
+
+const TUNNELS_FILE: &str = "tunnels.json";
+
+/// Serializes read-modify-write cycles on the tunnels file
+static STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// What a saved tunnel connects through
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TunnelTarget {
+    /// SSM-managed EC2 instance
+    Instance { instance_id: String },
+    /// A specific ECS task container
+    EcsTask {
+        cluster: String,
+        task: String,
+        container: String,
+        runtime_id: String,
+    },
+    /// Any running task of an ECS service, resolved on every (re)connect
+    EcsService {
+        cluster: String,
+        service: String,
+        container: String,
+    },
+}
+
+/// A saved port-forward tunnel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelDefinition {
+    pub id: String,
+    pub name: String,
+    pub profile: String,
+    pub region: String,
+    pub target: TunnelTarget,
+    pub local_port: u16,
+    pub remote_port: u16,
+    pub remote_host: Option<String>,
+    /// Start the tunnel when the app launches
+    #[serde(default)]
+    pub auto_start: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Input for creating or updating a saved tunnel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelInput {
+    pub name: String,
+    pub profile: String,
+    pub region: String,
+    pub target: TunnelTarget,
+    pub local_port: u16,
+    pub remote_port: u16,
+    pub remote_host: Option<String>,
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+/// Get the path of the saved tunnels file
+fn tunnels_path() -> Result<PathBuf, String> {
+    app_config_path(TUNNELS_FILE)
+}
+
+fn read_tunnels() -> Result<Vec<TunnelDefinition>, String> {
+    let path = tunnels_path()?;
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read saved tunnels: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse saved tunnels: {}", e))
+}
+
+fn write_tunnels(tunnels: &[TunnelDefinition]) -> Result<(), String> {
+    let path = tunnels_path()?;
+    let content = serde_json::to_string_pretty(tunnels)
+        .map_err(|e| format!("Failed to serialize tunnels: {}", e))?;
+
+    write_atomic(&path, content.as_bytes()).map_err(|e| format!("Failed to save tunnels: {}", e))
+}
+
+fn validate_input(input: &TunnelInput) -> Result<(), String> {
+    if input.name.trim().is_empty() {
+        return Err("Tunnel name is empty".to_string());
+    }
+    if input.local_port == 0 || input.remote_port == 0 {
+        return Err("Ports must be between 1 and 65535".to_string());
+    }
+    Ok(())
+}
+
+/// List all saved tunnels
+pub fn list_tunnels() -> Result<Vec<TunnelDefinition>, String> {
+    let _guard = STORE_LOCK.lock();
+    read_tunnels()
+}
+
+/// Get a saved tunnel by ID
+pub fn get_tunnel(id: &str) -> Result<TunnelDefinition, String> {
+    list_tunnels()?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Tunnel not found: {}", id))
+}
+
+/// Save a new tunnel
+pub fn create_tunnel(input: TunnelInput) -> Result<TunnelDefinition, String> {
+    validate_input(&input)?;
+    let _guard = STORE_LOCK.lock();
+
+    let mut tunnels = read_tunnels()?;
+    let now = chrono::Utc::now().timestamp_millis();
+    let tunnel = TunnelDefinition {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: input.name,
+        profile: input.profile,
+        region: input.region,
+        target: input.target,
+        local_port: input.local_port,
+        remote_port: input.remote_port,
+        remote_host: input.remote_host,
+        auto_start: input.auto_start,
+        created_at: now,
+        updated_at: now,
+    };
+
+    tunnels.push(tunnel.clone());
+    write_tunnels(&tunnels)?;
+
+    Ok(tunnel)
+}
+
+/// Replace the settings of a saved tunnel
+pub fn update_tunnel(id: &str, input: TunnelInput) -> Result<TunnelDefinition, String> {
+    validate_input(&input)?;
+    let _guard = STORE_LOCK.lock();
+
+    let mut tunnels = read_tunnels()?;
+    let tunnel = tunnels
+        .iter_mut()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Tunnel not found: {}", id))?;
+
+    tunnel.name = input.name;
+    tunnel.profile = input.profile;
+    tunnel.region = input.region;
+    tunnel.target = input.target;
+    tunnel.local_port = input.local_port;
+    tunnel.remote_port = input.remote_port;
+    tunnel.remote_host = input.remote_host;
+    tunnel.auto_start = input.auto_start;
+    tunnel.updated_at = chrono::Utc::now().timestamp_millis();
+
+    let updated = tunnel.clone();
+    write_tunnels(&tunnels)?;
+
+    Ok(updated)
+}
+
+/// Delete a saved tunnel
+pub fn delete_tunnel(id: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+
+    let mut tunnels = read_tunnels()?;
+    let count = tunnels.len();
+    tunnels.retain(|t| t.id != id);
+    if tunnels.len() == count {
+        return Err(format!("Tunnel not found: {}", id));
+    }
+
+    write_tunnels(&tunnels)
+}
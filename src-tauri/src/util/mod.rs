@@ -0,0 +1,3 @@
+pub mod fs;
+pub mod poll;
+pub mod shell;
@@ -0,0 +1,75 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Outcome of a single poll
+pub enum Polled<T> {
+    Ready(T),
+    /// The poll failed, try again after the interval
+    Failed(String),
+    /// The poll failed and the deadline has passed, stop polling
+    TimedOut(String),
+}
+
+/// Fixed-interval poller with a deadline. Failed polls are retried, the error may
+/// be temporary, but never past the deadline.
+pub struct Poller {
+    interval: Duration,
+    timeout: Duration,
+    started: Instant,
+}
+
+impl Poller {
+    pub fn new(interval: Duration, timeout: Duration) -> Self {
+        Self {
+            interval,
+            timeout,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn expired(&self) -> bool {
+        self.started.elapsed() >= self.timeout
+    }
+
+    /// Wait for the interval, then run one poll
+    pub async fn poll<T>(&self, request: impl Future<Output = Result<T, String>>) -> Polled<T> {
+        tokio::time::sleep(self.interval).await;
+        self.check(request.await)
+    }
+
+    /// Classify the result of a request made during the current poll
+    pub fn check<T>(&self, result: Result<T, String>) -> Polled<T> {
+        match result {
+            Ok(value) => Polled::Ready(value),
+            Err(e) if self.expired() => Polled::TimedOut(e),
+            Err(e) => Polled::Failed(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_stops_failing_polls_at_deadline() {
+        let poller = Poller::new(Duration::ZERO, Duration::from_secs(60));
+        assert!(matches!(
+            poller.check::<()>(Err("x".into())),
+            Polled::Failed(_)
+        ));
+        assert!(matches!(poller.check(Ok(1)), Polled::Ready(1)));
+
+        let expired = Poller::new(Duration::ZERO, Duration::ZERO);
+        assert!(matches!(
+            expired.check::<()>(Err("x".into())),
+            Polled::TimedOut(e) if e == "x"
+        ));
+        // Successful polls are left to the caller, which knows how to report them
+        assert!(matches!(expired.check(Ok(1)), Polled::Ready(1)));
+    }
+}
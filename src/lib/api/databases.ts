@@ -0,0 +1,32 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { Ec2Instance } from '$lib/types/aws';
+import type { ElastiCacheResources, RdsResources } from '$lib/types/databases';
+
+/**
+ * List RDS instances and Aurora clusters with their endpoints
+ */
+export async function listRdsDatabases(profile: string, region: string): Promise<RdsResources> {
+  return invoke<RdsResources>('list_rds_databases', { profile, region });
+}
+
+/**
+ * List ElastiCache replication groups and standalone cache clusters
+ */
+export async function listElastiCacheClusters(
+  profile: string,
+  region: string
+): Promise<ElastiCacheResources> {
+  return invoke<ElastiCacheResources>('list_elasticache_clusters', { profile, region });
+}
+
+/**
+ * Suggest SSM-enabled instances in a VPC to forward database ports through,
+ * best candidates first
+ */
+export async function suggestBastionInstances(
+  profile: string,
+  region: string,
+  vpcId: string
+): Promise<Ec2Instance[]> {
+  return invoke<Ec2Instance[]>('suggest_bastion_instances', { profile, region, vpcId });
+}
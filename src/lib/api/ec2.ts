@@ -0,0 +1,166 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  AutoScalingGroup,
+  ConsoleOutput,
+  InstanceAction,
+  InstanceActionOutput,
+  InstanceDetail,
+  InstancePowerProgress,
+  SshConfigEntry,
+} from '$lib/types/aws';
+
+/**
+ * Start, stop, reboot or hibernate an EC2 instance.
+ * With waitForSsm, progress continues until the SSM agent reports Online.
+ */
+export async function changeEc2InstanceState(
+  profile: string,
+  region: string,
+  instanceId: string,
+  action: InstanceAction,
+  waitForSsm = false
+): Promise<InstanceActionOutput> {
+  return invoke<InstanceActionOutput>('change_ec2_instance_state', {
+    profile,
+    region,
+    instanceId,
+    action,
+    waitForSsm,
+  });
+}
+
+/**
+ * Describe an instance's network, security groups, volumes, IAM profile and AMI
+ */
+export async function describeEc2InstanceDetail(
+  profile: string,
+  region: string,
+  instanceId: string
+): Promise<InstanceDetail> {
+  return invoke<InstanceDetail>('describe_ec2_instance_detail', { profile, region, instanceId });
+}
+
+/**
+ * Get the decoded serial console output of an instance
+ */
+export async function getEc2ConsoleOutput(
+  profile: string,
+  region: string,
+  instanceId: string
+): Promise<ConsoleOutput> {
+  return invoke<ConsoleOutput>('get_ec2_console_output', { profile, region, instanceId });
+}
+
+/**
+ * Take a console screenshot of an instance, as a base64 JPEG
+ */
+export async function getEc2ConsoleScreenshot(
+  profile: string,
+  region: string,
+  instanceId: string
+): Promise<string> {
+  return invoke<string>('get_ec2_console_screenshot', { profile, region, instanceId });
+}
+
+/**
+ * Add or update a ~/.ssh/config host that reaches an instance through SSM with an
+ * EC2 Instance Connect key, for scp, rsync and ansible. Returns the written block.
+ */
+export async function writeSshConfigEntry(entry: SshConfigEntry): Promise<string> {
+  return invoke<string>('write_ssh_config_entry', { entry });
+}
+
+/**
+ * Listen for instance power action progress
+ */
+export async function onInstancePowerProgress(
+  operationId: string,
+  callback: (progress: InstancePowerProgress) => void
+): Promise<UnlistenFn> {
+  return listen<InstancePowerProgress>(`ec2:power:progress:${operationId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for instance power action completion (completed, failed or timed out)
+ */
+export async function onInstancePowerDone(
+  operationId: string,
+  callback: (progress: InstancePowerProgress) => void
+): Promise<UnlistenFn> {
+  return listen<InstancePowerProgress>(`ec2:power:done:${operationId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * List Auto Scaling groups with their instances and latest instance refresh
+ */
+export async function listAutoScalingGroups(
+  profile: string,
+  region: string
+): Promise<AutoScalingGroup[]> {
+  return invoke<AutoScalingGroup[]>('list_auto_scaling_groups', { profile, region });
+}
+
+/**
+ * Change the desired capacity of an Auto Scaling group
+ */
+export async function setAsgDesiredCapacity(
+  profile: string,
+  region: string,
+  groupName: string,
+  desiredCapacity: number,
+  honorCooldown = false
+): Promise<void> {
+  return invoke('set_asg_desired_capacity', {
+    profile,
+    region,
+    groupName,
+    desiredCapacity,
+    honorCooldown,
+  });
+}
+
+/**
+ * Put instances into Standby (kept running, not replaced) or return them to service.
+ * Without decrementDesiredCapacity a replacement instance is launched.
+ */
+export async function setAsgInstanceStandby(
+  profile: string,
+  region: string,
+  groupName: string,
+  instanceIds: string[],
+  standby: boolean,
+  decrementDesiredCapacity = true
+): Promise<void> {
+  return invoke('set_asg_instance_standby', {
+    profile,
+    region,
+    groupName,
+    instanceIds,
+    standby,
+    decrementDesiredCapacity,
+  });
+}
+
+/**
+ * Start an instance refresh, returning its ID
+ */
+export async function startAsgInstanceRefresh(
+  profile: string,
+  region: string,
+  groupName: string,
+  minHealthyPercentage?: number,
+  instanceWarmupSecs?: number
+): Promise<string> {
+  return invoke<string>('start_asg_instance_refresh', {
+    profile,
+    region,
+    groupName,
+    minHealthyPercentage,
+    instanceWarmupSecs,
+  });
+}
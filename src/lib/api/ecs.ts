@@ -0,0 +1,399 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  BatchExecInput,
+  BatchExecReport,
+  BatchExecTargetResult,
+  ContainerCopyInput,
+  CopyProgress,
+  CopyResult,
+  EcsCapacityProvider,
+  EcsContainerInstance,
+  EcsNetworkConfig,
+  EcsServiceSnapshot,
+  EcsTaskRunInfo,
+  EcsTaskRunLogs,
+  EcsUtilizationMetrics,
+  EnableServiceExecOutput,
+  ExecPreflightReport,
+  ExecRolloutProgress,
+  RunTaskInput,
+  ServiceWatchEvent,
+  ServiceWatchInfo,
+  TaskEndpoint,
+} from '$lib/types/ecs';
+
+/**
+ * Run a one-off task and track it until it stops
+ */
+export async function runEcsTask(
+  profile: string,
+  region: string,
+  input: RunTaskInput
+): Promise<EcsTaskRunInfo> {
+  return invoke<EcsTaskRunInfo>('run_ecs_task', { profile, region, input });
+}
+
+/**
+ * Stop the task behind a tracked run
+ */
+export async function stopEcsTaskRun(runId: string): Promise<void> {
+  return invoke<void>('stop_ecs_task_run', { runId });
+}
+
+/**
+ * Get a tracked task run
+ */
+export async function getEcsTaskRun(runId: string): Promise<EcsTaskRunInfo> {
+  return invoke<EcsTaskRunInfo>('get_ecs_task_run', { runId });
+}
+
+/**
+ * List tracked task runs
+ */
+export async function listEcsTaskRuns(): Promise<EcsTaskRunInfo[]> {
+  return invoke<EcsTaskRunInfo[]>('list_ecs_task_runs');
+}
+
+/**
+ * Get the network configuration of a service
+ */
+export async function getEcsServiceNetworkConfig(
+  profile: string,
+  region: string,
+  cluster: string,
+  serviceName: string
+): Promise<EcsNetworkConfig | null> {
+  return invoke<EcsNetworkConfig | null>('get_ecs_service_network_config', {
+    profile,
+    region,
+    cluster,
+    serviceName,
+  });
+}
+
+/**
+ * Check ECS Exec readiness for a task/container before opening a session
+ */
+export async function ecsExecPreflight(
+  profile: string,
+  region: string,
+  cluster: string,
+  taskArn: string,
+  container: string
+): Promise<ExecPreflightReport> {
+  return invoke<ExecPreflightReport>('ecs_exec_preflight', {
+    profile,
+    region,
+    cluster,
+    taskArn,
+    container,
+  });
+}
+
+/**
+ * Listen for task run status changes
+ */
+export async function onTaskRunStatus(
+  runId: string,
+  callback: (info: EcsTaskRunInfo) => void
+): Promise<UnlistenFn> {
+  return listen<EcsTaskRunInfo>(`ecs:run:status:${runId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for task run container logs
+ */
+export async function onTaskRunLogs(
+  runId: string,
+  callback: (logs: EcsTaskRunLogs) => void
+): Promise<UnlistenFn> {
+  return listen<EcsTaskRunLogs>(`ecs:run:logs:${runId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for task run completion (payload includes container exit codes)
+ */
+export async function onTaskRunStopped(
+  runId: string,
+  callback: (info: EcsTaskRunInfo) => void
+): Promise<UnlistenFn> {
+  return listen<EcsTaskRunInfo>(`ecs:run:stopped:${runId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Enable execute command on a service, optionally forcing a new deployment
+ */
+export async function enableEcsServiceExec(
+  profile: string,
+  region: string,
+  cluster: string,
+  serviceName: string,
+  forceNewDeployment: boolean
+): Promise<EnableServiceExecOutput> {
+  return invoke<EnableServiceExecOutput>('enable_ecs_service_exec', {
+    profile,
+    region,
+    cluster,
+    serviceName,
+    forceNewDeployment,
+  });
+}
+
+/**
+ * Listen for exec rollout progress
+ */
+export async function onExecRolloutProgress(
+  rolloutId: string,
+  callback: (progress: ExecRolloutProgress) => void
+): Promise<UnlistenFn> {
+  return listen<ExecRolloutProgress>(`ecs:exec-rollout:progress:${rolloutId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for exec rollout completion (completed, failed or timed out)
+ */
+export async function onExecRolloutDone(
+  rolloutId: string,
+  callback: (progress: ExecRolloutProgress) => void
+): Promise<UnlistenFn> {
+  return listen<ExecRolloutProgress>(`ecs:exec-rollout:done:${rolloutId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+export interface MetricsWindow {
+  startTime?: number;
+  endTime?: number;
+  periodSecs?: number;
+}
+
+/**
+ * Get CPU/memory utilization for a cluster
+ */
+export async function getEcsClusterMetrics(
+  profile: string,
+  region: string,
+  cluster: string,
+  window?: MetricsWindow
+): Promise<EcsUtilizationMetrics> {
+  return invoke<EcsUtilizationMetrics>('get_ecs_cluster_metrics', {
+    profile,
+    region,
+    cluster,
+    startTime: window?.startTime ?? null,
+    endTime: window?.endTime ?? null,
+    periodSecs: window?.periodSecs ?? null,
+  });
+}
+
+/**
+ * Get CPU/memory utilization for a service
+ */
+export async function getEcsServiceMetrics(
+  profile: string,
+  region: string,
+  cluster: string,
+  serviceName: string,
+  window?: MetricsWindow
+): Promise<EcsUtilizationMetrics> {
+  return invoke<EcsUtilizationMetrics>('get_ecs_service_metrics', {
+    profile,
+    region,
+    cluster,
+    serviceName,
+    startTime: window?.startTime ?? null,
+    endTime: window?.endTime ?? null,
+    periodSecs: window?.periodSecs ?? null,
+  });
+}
+
+/**
+ * List the EC2 container instances of a cluster
+ */
+export async function listEcsContainerInstances(
+  profile: string,
+  region: string,
+  cluster: string
+): Promise<EcsContainerInstance[]> {
+  return invoke<EcsContainerInstance[]>('list_ecs_container_instances', {
+    profile,
+    region,
+    cluster,
+  });
+}
+
+/**
+ * List the capacity providers of a cluster
+ */
+export async function listEcsCapacityProviders(
+  profile: string,
+  region: string,
+  cluster: string
+): Promise<EcsCapacityProvider[]> {
+  return invoke<EcsCapacityProvider[]>('list_ecs_capacity_providers', {
+    profile,
+    region,
+    cluster,
+  });
+}
+
+/**
+ * Start watching services for events, deployment changes and count drift
+ */
+export async function startEcsServiceWatch(
+  profile: string,
+  region: string,
+  cluster: string,
+  services: string[],
+  options?: { alertAfterMinutes?: number; pollIntervalSecs?: number }
+): Promise<ServiceWatchInfo> {
+  return invoke<ServiceWatchInfo>('start_ecs_service_watch', {
+    profile,
+    region,
+    cluster,
+    services,
+    alertAfterMinutes: options?.alertAfterMinutes ?? null,
+    pollIntervalSecs: options?.pollIntervalSecs ?? null,
+  });
+}
+
+/**
+ * Stop a service watch session
+ */
+export async function stopEcsServiceWatch(watchId: string): Promise<void> {
+  return invoke<void>('stop_ecs_service_watch', { watchId });
+}
+
+/**
+ * List active service watch sessions
+ */
+export async function listEcsServiceWatches(): Promise<ServiceWatchInfo[]> {
+  return invoke<ServiceWatchInfo[]>('list_ecs_service_watches');
+}
+
+/**
+ * Listen for changes detected by a service watch
+ */
+export async function onServiceWatchEvent(
+  watchId: string,
+  callback: (event: ServiceWatchEvent) => void
+): Promise<UnlistenFn> {
+  return listen<ServiceWatchEvent>(`ecs:watch:event:${watchId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for the latest state of watched services (every poll)
+ */
+export async function onServiceWatchSnapshot(
+  watchId: string,
+  callback: (snapshots: EcsServiceSnapshot[]) => void
+): Promise<UnlistenFn> {
+  return listen<EcsServiceSnapshot[]>(`ecs:watch:snapshot:${watchId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Download a file (or an archive of a directory) from a container over ECS Exec
+ */
+export async function ecsCopyFromContainer(
+  profile: string,
+  region: string,
+  input: ContainerCopyInput
+): Promise<CopyResult> {
+  return invoke<CopyResult>('ecs_copy_from_container', { profile, region, input });
+}
+
+/**
+ * Upload a local file into a container over ECS Exec
+ */
+export async function ecsCopyToContainer(
+  profile: string,
+  region: string,
+  input: ContainerCopyInput
+): Promise<CopyResult> {
+  return invoke<CopyResult>('ecs_copy_to_container', { profile, region, input });
+}
+
+/**
+ * Listen for progress of container file transfers
+ */
+export async function onCopyProgress(
+  callback: (progress: CopyProgress) => void
+): Promise<UnlistenFn> {
+  return listen<CopyProgress>('ecs:copy:progress', (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Run one command on many containers in parallel and return a combined report
+ */
+export async function ecsBatchExec(
+  profile: string,
+  region: string,
+  input: BatchExecInput
+): Promise<BatchExecReport> {
+  return invoke<BatchExecReport>('ecs_batch_exec', { profile, region, input });
+}
+
+/**
+ * Listen for per-target results of a batch exec as they complete
+ */
+export async function onBatchExecResult(
+  batchId: string,
+  callback: (result: BatchExecTargetResult) => void
+): Promise<UnlistenFn> {
+  return listen<BatchExecTargetResult>(`ecs:batch:result:${batchId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Resolve the private IP and port of a task, for pre-filling a port forward
+ */
+export async function getEcsTaskEndpoint(
+  profile: string,
+  region: string,
+  cluster: string,
+  taskArn: string,
+  containerName?: string,
+  containerPort?: number
+): Promise<TaskEndpoint> {
+  return invoke<TaskEndpoint>('get_ecs_task_endpoint', {
+    profile,
+    region,
+    cluster,
+    taskArn,
+    containerName,
+    containerPort,
+  });
+}
+
+/**
+ * Resolve the private IP and port of a running task of a service
+ */
+export async function getEcsServiceEndpoint(
+  profile: string,
+  region: string,
+  cluster: string,
+  serviceName: string
+): Promise<TaskEndpoint> {
+  return invoke<TaskEndpoint>('get_ecs_service_endpoint', {
+    profile,
+    region,
+    cluster,
+    serviceName,
+  });
+}
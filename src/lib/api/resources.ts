@@ -5,6 +5,8 @@ import type {
   EcsService,
   EcsTask,
   Ec2Instance,
+  InstanceFilters,
+  InstanceGroup,
 } from '$lib/types/aws';
 
 /**
@@ -60,11 +62,34 @@ export async function listEcsTasks(
 }
 
 /**
- * List SSM-enabled EC2 instances
+ * List SSM-enabled EC2 instances matching server-side filters (running ones by default)
  */
 export async function listEc2Instances(
   profile: string,
-  region: string
+  region: string,
+  filters?: InstanceFilters
 ): Promise<Ec2Instance[]> {
-  return invoke<Ec2Instance[]>('list_ec2_instances', { profile, region });
+  return invoke<Ec2Instance[]>('list_ec2_instances', {
+    profile,
+    region,
+    filters: filters ?? null,
+  });
+}
+
+/**
+ * List SSM-enabled EC2 instances grouped by tag values, one level per key in groupBy,
+ * e.g. ['Environment', 'aws:autoscaling:groupName']
+ */
+export async function listEc2InstanceGroups(
+  profile: string,
+  region: string,
+  groupBy: string[],
+  filters?: InstanceFilters
+): Promise<InstanceGroup[]> {
+  return invoke<InstanceGroup[]>('list_ec2_instance_groups', {
+    profile,
+    region,
+    groupBy,
+    filters: filters ?? null,
+  });
 }
@@ -0,0 +1,128 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  CommandHistoryEntry,
+  CommandInvocation,
+  RunCommandInput,
+  RunCommandStatus,
+  SessionDocumentDetail,
+  SessionDocumentSummary,
+  SessionListState,
+  SsmSessionRecord,
+} from '$lib/types/ssm';
+
+/**
+ * Send a shell/PowerShell script to instances with SSM Run Command.
+ * Returns the command ID that results are emitted under.
+ */
+export async function sendSsmCommand(
+  profile: string,
+  region: string,
+  input: RunCommandInput
+): Promise<string> {
+  return invoke<string>('send_ssm_command', { profile, region, input });
+}
+
+/**
+ * Run a command from the history again, with the same targets and profile
+ */
+export async function rerunSsmCommand(commandId: string): Promise<string> {
+  return invoke<string>('rerun_ssm_command', { commandId });
+}
+
+/**
+ * Get the per-instance results of a command, with output for finished instances
+ */
+export async function getSsmCommandInvocations(
+  profile: string,
+  region: string,
+  commandId: string
+): Promise<CommandInvocation[]> {
+  return invoke<CommandInvocation[]>('get_ssm_command_invocations', {
+    profile,
+    region,
+    commandId,
+  });
+}
+
+/**
+ * List past Run Commands, newest first
+ */
+export async function listSsmCommandHistory(): Promise<CommandHistoryEntry[]> {
+  return invoke<CommandHistoryEntry[]>('list_ssm_command_history');
+}
+
+/**
+ * Clear the Run Command history
+ */
+export async function clearSsmCommandHistory(): Promise<void> {
+  return invoke<void>('clear_ssm_command_history');
+}
+
+/**
+ * Listen for per-instance status and output of a command
+ */
+export async function onSsmCommandInvocation(
+  commandId: string,
+  callback: (invocation: CommandInvocation) => void
+): Promise<UnlistenFn> {
+  return listen<CommandInvocation>(`ssm:command:invocation:${commandId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Listen for a command finishing on all of its targets
+ */
+export async function onSsmCommandDone(
+  commandId: string,
+  callback: (status: RunCommandStatus) => void
+): Promise<UnlistenFn> {
+  return listen<RunCommandStatus>(`ssm:command:done:${commandId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * List the Session Manager documents a session can be started with
+ */
+export async function listSsmSessionDocuments(
+  profile: string,
+  region: string
+): Promise<SessionDocumentSummary[]> {
+  return invoke<SessionDocumentSummary[]>('list_ssm_session_documents', { profile, region });
+}
+
+/**
+ * Get the parameters of a Session document
+ */
+export async function describeSsmSessionDocument(
+  profile: string,
+  region: string,
+  name: string
+): Promise<SessionDocumentDetail> {
+  return invoke<SessionDocumentDetail>('describe_ssm_session_document', { profile, region, name });
+}
+
+/**
+ * List active or past Session Manager sessions, optionally for one target
+ */
+export async function listSsmSessions(
+  profile: string,
+  region: string,
+  state: SessionListState,
+  target?: string
+): Promise<SsmSessionRecord[]> {
+  return invoke<SsmSessionRecord[]>('list_ssm_sessions', { profile, region, state, target });
+}
+
+/**
+ * Terminate any active Session Manager session, including ones started elsewhere
+ */
+export async function terminateSsmSession(
+  profile: string,
+  region: string,
+  sessionId: string
+): Promise<void> {
+  return invoke('terminate_ssm_session', { profile, region, sessionId });
+}
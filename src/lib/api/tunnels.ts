@@ -0,0 +1,91 @@
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  ClientConnection,
+  ConnectionInfo,
+  IamAuthTarget,
+  LocalPortStatus,
+  TunnelDefinition,
+  TunnelInput,
+  TunnelState,
+} from '$lib/types/tunnels';
+
+/**
+ * List saved port-forward tunnels
+ */
+export async function listSavedTunnels(): Promise<TunnelDefinition[]> {
+  return invoke<TunnelDefinition[]>('list_saved_tunnels');
+}
+
+/**
+ * Save a new tunnel, or update an existing one when tunnelId is given
+ */
+export async function saveTunnel(
+  input: TunnelInput,
+  tunnelId?: string
+): Promise<TunnelDefinition> {
+  return invoke<TunnelDefinition>('save_tunnel', {
+    tunnelId: tunnelId ?? null,
+    input,
+  });
+}
+
+/**
+ * Delete a saved tunnel, stopping it if it is running
+ */
+export async function deleteSavedTunnel(tunnelId: string): Promise<void> {
+  return invoke<void>('delete_saved_tunnel', { tunnelId });
+}
+
+/**
+ * Start a saved tunnel and keep it connected
+ */
+export async function startTunnel(tunnelId: string): Promise<TunnelState> {
+  return invoke<TunnelState>('start_tunnel', { tunnelId });
+}
+
+/**
+ * Stop a running tunnel
+ */
+export async function stopTunnel(tunnelId: string): Promise<void> {
+  return invoke<void>('stop_tunnel', { tunnelId });
+}
+
+/**
+ * List the state of all started tunnels
+ */
+export async function listTunnelStates(): Promise<TunnelState[]> {
+  return invoke<TunnelState[]>('list_tunnel_states');
+}
+
+/**
+ * Listen for status changes of a tunnel
+ */
+export async function onTunnelStatus(
+  tunnelId: string,
+  callback: (state: TunnelState) => void
+): Promise<UnlistenFn> {
+  return listen<TunnelState>(`tunnel:status:${tunnelId}`, (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Check whether a local port is free, and who holds it if not
+ */
+export async function checkLocalPort(port: number): Promise<LocalPortStatus> {
+  return invoke<LocalPortStatus>('check_local_port', { port });
+}
+
+/**
+ * Build client connection strings for a tunnel, minting an RDS IAM auth token if requested
+ */
+export async function getTunnelConnectionInfo(
+  connection: ClientConnection,
+  iamAuth?: IamAuthTarget
+): Promise<ConnectionInfo> {
+  return invoke<ConnectionInfo>('get_tunnel_connection_info', {
+    connection,
+    iamAuth: iamAuth ?? null,
+  });
+}
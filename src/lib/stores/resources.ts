@@ -1,13 +1,28 @@
 import { writable, derived, get } from 'svelte/store';
-import type { DiscoveredResources, EcsCluster, EcsService, EcsTask, Ec2Instance } from '$lib/types/aws';
-import { discoverResources } from '$lib/api/resources';
+import type {
+  DiscoveredResources,
+  EcsCluster,
+  EcsService,
+  EcsTask,
+  EcsTaskGroup,
+  Ec2Instance,
+  InstanceAction,
+  InstanceGroup,
+  InstancePowerProgress,
+} from '$lib/types/aws';
+import { discoverResources, listEc2InstanceGroups } from '$lib/api/resources';
+import { changeEc2InstanceState, onInstancePowerDone } from '$lib/api/ec2';
 import { selectedProfile, selectedRegion } from './profiles';
+import { settings } from './settings';
 
 // Store for discovered resources
 export const resources = writable<DiscoveredResources | null>(null);
 export const resourcesLoading = writable<boolean>(false);
 export const resourcesError = writable<string | null>(null);
 
+// EC2 instances grouped by the tag keys in the ec2GroupBy setting (null when not grouped)
+export const instanceGroups = writable<InstanceGroup[] | null>(null);
+
 // Expanded state for tree nodes
 export const expandedNodes = writable<Set<string>>(new Set());
 
@@ -37,6 +52,14 @@ export function getTasksForService(clusterArn: string, serviceName: string): Ecs
   return $resources?.ecs.tasks[key] ?? [];
 }
 
+/**
+ * Get standalone (non-service) tasks for a cluster, grouped by task group or started-by
+ */
+export function getStandaloneTaskGroups(clusterArn: string): EcsTaskGroup[] {
+  const $resources = get(resources);
+  return $resources?.ecs.standalone_tasks[clusterArn] ?? [];
+}
+
 /**
  * Load AWS resources
  */
@@ -63,6 +86,8 @@ export async function loadResources(): Promise<void> {
       }
     }
     expandedNodes.set(toExpand);
+
+    loadInstanceGroups();
   } catch (error) {
     resourcesError.set(String(error));
     console.error('Failed to load AWS resources:', error);
@@ -71,6 +96,73 @@ export async function loadResources(): Promise<void> {
   }
 }
 
+/**
+ * Load EC2 instance groups for the ec2GroupBy setting, in every state like the flat list
+ */
+export async function loadInstanceGroups(): Promise<void> {
+  const profile = get(selectedProfile);
+  const region = get(selectedRegion);
+  const groupBy = get(settings).ec2GroupBy;
+
+  if (!profile || !region || groupBy.length === 0) {
+    instanceGroups.set(null);
+    return;
+  }
+
+  try {
+    instanceGroups.set(
+      await listEc2InstanceGroups(profile, region, groupBy, { all_states: true })
+    );
+  } catch (error) {
+    // The tree falls back to the flat list
+    instanceGroups.set(null);
+    console.error('Failed to load EC2 instance groups:', error);
+  }
+}
+
+/**
+ * Change the tag keys EC2 instances are grouped by and reload the groups
+ */
+export async function setInstanceGroupBy(groupBy: string[]): Promise<void> {
+  settings.setSetting('ec2GroupBy', groupBy);
+  await loadInstanceGroups();
+}
+
+/**
+ * Start, stop, reboot or hibernate an instance and wait for the action to finish, then
+ * reload resources so the tree shows the new state. Rejects when it fails or times out.
+ */
+export async function changeInstanceState(
+  profile: string,
+  region: string,
+  instanceId: string,
+  action: InstanceAction,
+  waitForSsm = false
+): Promise<InstancePowerProgress> {
+  const { operation_id } = await changeEc2InstanceState(
+    profile,
+    region,
+    instanceId,
+    action,
+    waitForSsm
+  );
+
+  // The first progress poll runs seconds after the request, so this can't miss the event
+  const progress = await new Promise<InstancePowerProgress>((resolve) => {
+    const unlisten = onInstancePowerDone(operation_id, (p) => {
+      unlisten.then((fn) => fn());
+      resolve(p);
+    });
+  });
+
+  loadResources();
+
+  if (progress.phase !== 'completed') {
+    throw new Error(progress.message ?? `Instance did not ${action} (${progress.phase})`);
+  }
+  return progress;
+}
+
 /**
  * Toggle node expansion
  */
@@ -98,6 +190,7 @@ export function isNodeExpanded(nodeId: string): boolean {
  */
 export function clearResources(): void {
   resources.set(null);
+  instanceGroups.set(null);
   expandedNodes.set(new Set());
   resourcesError.set(null);
 }
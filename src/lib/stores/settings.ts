@@ -13,6 +13,9 @@ export interface AppSettings {
   // Behavior
   confirmBeforeClose: boolean;
   autoRefreshInterval: number; // 0 = disabled, otherwise seconds
+
+  // Resource tree
+  ec2GroupBy: string[]; // tag keys, one tree level per key, empty = flat list
 }
 
 // Default settings
@@ -23,6 +26,7 @@ const defaultSettings: AppSettings = {
   defaultShell: '/bin/sh',
   confirmBeforeClose: true,
   autoRefreshInterval: 0,
+  ec2GroupBy: [],
 };
 
 // Storage key
@@ -5,6 +5,7 @@ import {
   closeTerminal,
   listTerminalSessions,
 } from '$lib/api/terminal';
+import { changeInstanceState } from './resources';
 
 // All terminal sessions
 export const sessions = writable<Map<string, TerminalSession>>(new Map());
@@ -54,6 +55,24 @@ export async function createSession(input: CreateSessionInput): Promise<string>
   return result.session_id;
 }
 
+/**
+ * Start an EC2 instance and open an SSM session once its agent reports Online.
+ * Resolves with the session ID, or rejects when the instance doesn't become ready.
+ */
+export async function startInstanceAndConnect(
+  profile: string,
+  region: string,
+  instanceId: string,
+  title?: string
+): Promise<string> {
+  await changeInstanceState(profile, region, instanceId, 'start', true);
+
+  return createSession({
+    session_type: { type: 'ssm_session', instance_id: instanceId, profile, region },
+    title,
+  });
+}
+
 /**
  * Close a terminal session
  */
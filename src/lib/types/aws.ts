@@ -51,6 +51,7 @@ export interface EcsCluster {
   status: string;
   running_tasks_count: number;
   services_count: number;
+  container_insights: string | null;
 }
 
 export interface EcsService {
@@ -61,6 +62,41 @@ export interface EcsService {
   desired_count: number;
   running_count: number;
   launch_type: string | null;
+  enable_execute_command: boolean;
+  rollout_state: string | null;
+  load_balancers: EcsLoadBalancer[];
+  /** Cloud Map service registries */
+  service_registries: EcsServiceRegistry[];
+  /** Service Connect configuration of the primary deployment */
+  service_connect: EcsServiceConnect | null;
+}
+
+export interface EcsLoadBalancer {
+  target_group_arn: string | null;
+  /** Only set for Classic Load Balancers */
+  load_balancer_name: string | null;
+  container_name: string | null;
+  container_port: number | null;
+}
+
+export interface EcsServiceRegistry {
+  registry_arn: string | null;
+  port: number | null;
+  container_name: string | null;
+  container_port: number | null;
+}
+
+export interface EcsServiceConnect {
+  enabled: boolean;
+  namespace: string | null;
+  services: EcsServiceConnectService[];
+}
+
+export interface EcsServiceConnectService {
+  port_name: string;
+  discovery_name: string | null;
+  ingress_port_override: number | null;
+  client_aliases: { port: number; dns_name: string | null }[];
 }
 
 export interface EcsContainer {
@@ -68,6 +104,9 @@ export interface EcsContainer {
   runtime_id: string | null;
   last_status: string;
   health_status: string | null;
+  exit_code: number | null;
+  reason: string | null;
+  exec_agent_status: string | null;
 }
 
 export interface EcsTask {
@@ -79,12 +118,25 @@ export interface EcsTask {
   launch_type: string | null;
   containers: EcsContainer[];
   enable_execute_command: boolean;
+  stopped_reason: string | null;
+  /** `service:<name>` for service tasks, `family:<name>` by default otherwise */
+  group: string | null;
+  /** e.g. `events-rule/nightly` for scheduled tasks */
+  started_by: string | null;
+  started_at: number | null;
+}
+
+export interface EcsTaskGroup {
+  name: string;
+  tasks: EcsTask[];
 }
 
 export interface EcsResources {
   clusters: EcsCluster[];
   services: Record<string, EcsService[]>;
   tasks: Record<string, EcsTask[]>;
+  /** Running tasks that belong to no service, keyed by cluster ARN */
+  standalone_tasks: Record<string, EcsTaskGroup[]>;
 }
 
 // EC2 Types
@@ -96,8 +148,186 @@ export interface Ec2Instance {
   private_ip: string | null;
   public_ip: string | null;
   platform: string | null;
+  /** Launched with hibernation enabled, so it can be hibernated instead of stopped */
+  hibernation_configured: boolean;
   ssm_enabled: boolean;
   ssm_ping_status: string | null;
+  ssm_agent_version: string | null;
+  /** SSM reports a newer agent release than the installed one */
+  ssm_agent_outdated: boolean;
+  /** OS as reported by the SSM agent */
+  ssm_platform_name: string | null;
+  ssm_platform_version: string | null;
+  ssm_last_ping_at: number | null;
+  /** Status of the last State Manager association run */
+  ssm_association_status: string | null;
+  /** IP address the SSM agent reports */
+  ssm_ip_address: string | null;
+  vpc_id: string | null;
+  subnet_id: string | null;
+  tags: Record<string, string>;
+}
+
+export interface TagFilter {
+  key: string;
+  /** Any value matches when empty */
+  values?: string[];
+}
+
+/** Server-side filters for listing instances */
+export interface InstanceFilters {
+  tags?: TagFilter[];
+  vpc_ids?: string[];
+  subnet_ids?: string[];
+  image_ids?: string[];
+  /** Include instances in every state, not only running ones */
+  all_states?: boolean;
+}
+
+/** Instances sharing a tag value, possibly split further by the next tag key */
+export interface InstanceGroup {
+  tag_key: string;
+  /** null for instances without the tag */
+  value: string | null;
+  instance_count: number;
+  /** Subgroups by the next tag key, empty at the last level */
+  groups: InstanceGroup[];
+  /** Instances of the group, only filled at the last level */
+  instances: Ec2Instance[];
+}
+
+export type InstanceAction = 'start' | 'stop' | 'reboot' | 'hibernate';
+
+export type InstancePowerPhase =
+  | 'transitioning'
+  | 'waiting_for_agent'
+  | 'completed'
+  | 'failed'
+  | 'timed_out';
+
+export interface InstancePowerProgress {
+  id: string;
+  instance_id: string;
+  action: InstanceAction;
+  phase: InstancePowerPhase;
+  state: string | null;
+  ssm_ping_status: string | null;
+  message: string | null;
+}
+
+export interface InstanceActionOutput {
+  /** ID for the power progress events */
+  operation_id: string;
+  /** State reported right after the request (not available for reboots) */
+  state: string | null;
+}
+
+/** Everything about an instance that matters when debugging connectivity */
+export interface InstanceDetail {
+  instance: Ec2Instance;
+  vpc_cidr: string | null;
+  subnet_cidr: string | null;
+  availability_zone: string | null;
+  private_dns_name: string | null;
+  public_dns_name: string | null;
+  security_groups: SecurityGroupDetail[];
+  volumes: InstanceVolume[];
+  /** ARN of the IAM instance profile */
+  iam_instance_profile: string | null;
+  launch_time: number | null;
+  image_id: string | null;
+  /** null once the AMI is deregistered or no longer shared */
+  image_name: string | null;
+  key_name: string | null;
+  architecture: string | null;
+}
+
+export interface SecurityGroupDetail {
+  group_id: string;
+  group_name: string | null;
+  description: string | null;
+  inbound_rules: SecurityGroupRule[];
+}
+
+/** One inbound rule source */
+export interface SecurityGroupRule {
+  /** "tcp", "udp", "icmp", ... or "all" */
+  protocol: string;
+  /** null when the rule covers all ports */
+  from_port: number | null;
+  to_port: number | null;
+  /** IPv4/IPv6 CIDR, security group ID or prefix list ID */
+  source: string;
+  description: string | null;
+}
+
+export interface InstanceVolume {
+  volume_id: string;
+  device_name: string | null;
+  size_gib: number | null;
+  volume_type: string | null;
+  iops: number | null;
+  encrypted: boolean;
+  delete_on_termination: boolean;
+  state: string | null;
+}
+
+/** Serial console output of an instance */
+export interface ConsoleOutput {
+  instance_id: string;
+  output: string;
+  /** When the output was last updated */
+  timestamp: number | null;
+  /** Whether this is the latest output, only Nitro instances support it */
+  latest: boolean;
+}
+
+/** ~/.ssh/config host reaching an instance over SSM with an Instance Connect key */
+export interface SshConfigEntry {
+  /** Name to ssh/scp/rsync to, e.g. "api-bastion" */
+  host_alias: string;
+  instance_id: string;
+  os_user: string;
+  profile: string;
+  region: string;
+}
+
+// Auto Scaling Types
+export interface AutoScalingGroup {
+  name: string;
+  arn: string | null;
+  min_size: number;
+  max_size: number;
+  desired_capacity: number;
+  /** Launch template name, or launch configuration name for older groups */
+  launch_template: string | null;
+  health_check_type: string | null;
+  /** Set while the group is being deleted */
+  status: string | null;
+  instances: AsgInstance[];
+  /** Most recent instance refresh */
+  instance_refresh: AsgInstanceRefresh | null;
+}
+
+export interface AsgInstance {
+  instance_id: string;
+  /** Pending, InService, Standby, Terminating, ... */
+  lifecycle_state: string;
+  health_status: string | null;
+  availability_zone: string | null;
+  protected_from_scale_in: boolean;
+  /** Matching EC2 instance (for opening an SSM session) */
+  ec2_instance: Ec2Instance | null;
+}
+
+export interface AsgInstanceRefresh {
+  id: string;
+  status: string;
+  status_reason: string | null;
+  percentage_complete: number | null;
+  instances_to_update: number | null;
+  started_at: number | null;
+  finished_at: number | null;
 }
 
 // Combined Resources
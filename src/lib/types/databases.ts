@@ -0,0 +1,70 @@
+export interface RdsInstance {
+  identifier: string;
+  engine: string;
+  engine_version: string | null;
+  instance_class: string | null;
+  status: string;
+  endpoint: string | null;
+  port: number | null;
+  vpc_id: string | null;
+  availability_zone: string | null;
+  /** Aurora cluster the instance belongs to */
+  cluster_identifier: string | null;
+  master_username: string | null;
+  iam_auth_enabled: boolean;
+}
+
+export interface RdsCluster {
+  identifier: string;
+  engine: string;
+  engine_version: string | null;
+  status: string;
+  /** Writer endpoint */
+  endpoint: string | null;
+  reader_endpoint: string | null;
+  port: number | null;
+  vpc_id: string | null;
+  members: string[];
+  master_username: string | null;
+  iam_auth_enabled: boolean;
+}
+
+export interface RdsResources {
+  instances: RdsInstance[];
+  clusters: RdsCluster[];
+}
+
+export interface ElastiCacheReplicationGroup {
+  id: string;
+  description: string | null;
+  engine: string | null;
+  status: string;
+  /** Primary endpoint, cluster mode disabled only */
+  primary_endpoint: string | null;
+  reader_endpoint: string | null;
+  /** Configuration endpoint, cluster mode enabled only */
+  configuration_endpoint: string | null;
+  port: number | null;
+  cluster_mode: boolean;
+  transit_encryption: boolean;
+  vpc_id: string | null;
+  member_clusters: string[];
+}
+
+export interface ElastiCacheCluster {
+  id: string;
+  engine: string;
+  engine_version: string | null;
+  node_type: string | null;
+  status: string;
+  /** Configuration endpoint for Memcached, node endpoint otherwise */
+  endpoint: string | null;
+  port: number | null;
+  transit_encryption: boolean;
+  vpc_id: string | null;
+}
+
+export interface ElastiCacheResources {
+  replication_groups: ElastiCacheReplicationGroup[];
+  clusters: ElastiCacheCluster[];
+}
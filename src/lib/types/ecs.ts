@@ -0,0 +1,276 @@
+import type { Ec2Instance, EcsService, EcsTask } from './aws';
+import type { LogEvent } from './logs';
+
+export interface EcsNetworkConfig {
+  subnets: string[];
+  security_groups: string[];
+  assign_public_ip: boolean;
+}
+
+export interface ContainerOverrideInput {
+  name: string;
+  command?: string[] | null;
+  environment?: Record<string, string>;
+}
+
+export interface RunTaskInput {
+  cluster: string;
+  task_definition: string;
+  container_overrides?: ContainerOverrideInput[];
+  source_service?: string | null;
+  network_config?: EcsNetworkConfig | null;
+  launch_type?: string | null;
+  enable_execute_command?: boolean;
+}
+
+export interface EcsTaskRunInfo {
+  id: string;
+  cluster: string;
+  profile: string;
+  region: string;
+  status: 'running' | 'stopped' | 'error';
+  task: EcsTask;
+  created_at: number;
+  finished_at: number | null;
+}
+
+export interface EcsTaskRunLogs {
+  container: string;
+  events: LogEvent[];
+}
+
+export type PreflightCheckStatus = 'pass' | 'fail' | 'warn' | 'skipped';
+
+export interface PreflightCheck {
+  id: string;
+  name: string;
+  status: PreflightCheckStatus;
+  message: string;
+  remediation: string | null;
+}
+
+export interface ExecPreflightReport {
+  task_arn: string;
+  container: string;
+  ready: boolean;
+  checks: PreflightCheck[];
+}
+
+export interface EnableServiceExecOutput {
+  service: EcsService;
+  rollout_id: string | null;
+}
+
+export interface ExecRolloutProgress {
+  id: string;
+  cluster: string;
+  service: string;
+  phase: 'deploying' | 'completed' | 'failed' | 'timed_out';
+  desired_count: number;
+  running_count: number;
+  exec_ready_count: number;
+  legacy_task_count: number;
+  rollout_state: string | null;
+  message: string | null;
+}
+
+export interface MetricSeries {
+  id: string;
+  label: string;
+  unit: string;
+  timestamps: number[];
+  values: number[];
+}
+
+export interface EcsUtilizationMetrics {
+  cluster_name: string;
+  service_name: string | null;
+  source: 'container_insights' | 'aws_ecs';
+  start_time: number;
+  end_time: number;
+  period_secs: number;
+  series: MetricSeries[];
+}
+
+export interface EcsContainerInstance {
+  arn: string;
+  ec2_instance_id: string | null;
+  status: string;
+  agent_connected: boolean;
+  agent_version: string | null;
+  docker_version: string | null;
+  capacity_provider_name: string | null;
+  registered_cpu: number;
+  remaining_cpu: number;
+  registered_memory: number;
+  remaining_memory: number;
+  running_tasks_count: number;
+  pending_tasks_count: number;
+  ec2_instance: Ec2Instance | null;
+}
+
+export interface EcsCapacityProvider {
+  name: string;
+  arn: string | null;
+  status: string | null;
+  auto_scaling_group_arn: string | null;
+  managed_scaling_status: string | null;
+  target_capacity: number | null;
+  managed_termination_protection: string | null;
+  default_weight: number | null;
+  default_base: number | null;
+}
+
+export interface EcsServiceEvent {
+  id: string;
+  created_at: number | null;
+  message: string;
+}
+
+export interface EcsDeployment {
+  id: string;
+  status: string;
+  task_definition: string | null;
+  rollout_state: string | null;
+  rollout_state_reason: string | null;
+  desired_count: number;
+  running_count: number;
+  pending_count: number;
+  failed_tasks: number;
+  created_at: number | null;
+  updated_at: number | null;
+}
+
+export interface EcsServiceSnapshot {
+  service: EcsService;
+  pending_count: number;
+  events: EcsServiceEvent[];
+  deployments: EcsDeployment[];
+}
+
+export interface ServiceWatchInfo {
+  id: string;
+  cluster: string;
+  services: string[];
+  profile: string;
+  region: string;
+  alert_after_minutes: number | null;
+  poll_interval_secs: number;
+  status: 'running' | 'stopped';
+  created_at: number;
+}
+
+export type ServiceWatchEvent =
+  | { kind: 'service_events'; service: string; events: EcsServiceEvent[] }
+  | {
+      kind: 'deployment_changed';
+      service: string;
+      deployment: EcsDeployment;
+      previous_status: string | null;
+      previous_rollout_state: string | null;
+    }
+  | {
+      kind: 'count_drift';
+      service: string;
+      running_count: number;
+      pending_count: number;
+      desired_count: number;
+    }
+  | {
+      kind: 'below_desired_alert';
+      service: string;
+      running_count: number;
+      desired_count: number;
+      below_since: number;
+    }
+  | {
+      kind: 'recovered';
+      service: string;
+      running_count: number;
+      desired_count: number;
+      below_since: number;
+    };
+
+export interface ExecTarget {
+  cluster: string;
+  task: string;
+  container: string;
+}
+
+export interface ContainerCopyInput {
+  target: ExecTarget;
+  remote_path: string;
+  local_path: string;
+  /** Copy a directory (or file) as a .tar.gz archive, download only */
+  archive?: boolean;
+  /** ID used in progress events, generated when not provided */
+  transfer_id?: string;
+}
+
+export type CopyDirection = 'from_container' | 'to_container';
+
+export interface CopyProgress {
+  transfer_id: string;
+  direction: CopyDirection;
+  remote_path: string;
+  local_path: string;
+  bytes_transferred: number;
+  total_bytes: number | null;
+}
+
+export interface CopyResult {
+  transfer_id: string;
+  direction: CopyDirection;
+  remote_path: string;
+  local_path: string;
+  bytes: number;
+  sha256: string;
+  /** False when the container has no sha256sum and only the size was compared */
+  checksum_verified: boolean;
+}
+
+export interface BatchExecInput {
+  command: string;
+  targets: ExecTarget[];
+  /** Maximum number of exec sessions running at once (default 5) */
+  concurrency?: number;
+  /** Kill a session after this many seconds without output (default 60) */
+  idle_timeout_secs?: number;
+  /** ID used in result events, generated when not provided */
+  batch_id?: string;
+}
+
+export type BatchTargetStatus = 'succeeded' | 'failed' | 'error';
+
+export interface BatchExecTargetResult {
+  target: ExecTarget;
+  status: BatchTargetStatus;
+  exit_code: number | null;
+  stdout: string;
+  /** Empty when the container has no writable temp directory, stderr is then part of stdout */
+  stderr: string;
+  truncated: boolean;
+  error: string | null;
+  started_at: number;
+  finished_at: number;
+}
+
+export interface BatchExecReport {
+  batch_id: string;
+  command: string;
+  results: BatchExecTargetResult[];
+  succeeded: number;
+  failed: number;
+  errors: number;
+  started_at: number;
+  finished_at: number;
+}
+
+export interface TaskEndpoint {
+  task_arn: string;
+  /** ENI address in awsvpc mode, otherwise the private IP of the container instance */
+  private_ip: string | null;
+  /** Port reachable at private_ip (the host port in bridge/host network mode) */
+  port: number | null;
+  container_name: string | null;
+}
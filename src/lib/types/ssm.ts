@@ -0,0 +1,98 @@
+export type CommandTargets =
+  | { type: 'instances'; instance_ids: string[] }
+  /** Every managed instance with tag key set to one of values */
+  | { type: 'tag'; key: string; values: string[] };
+
+export type CommandShell = 'shell' | 'power_shell';
+
+export interface RunCommandInput {
+  shell?: CommandShell;
+  script: string;
+  targets: CommandTargets;
+  comment?: string | null;
+  timeout_secs?: number | null;
+  /** Instances to run on at once, a number or a percentage ("10", "25%") */
+  max_concurrency?: string | null;
+  /** Errors after which the command stops being sent to more instances */
+  max_errors?: string | null;
+}
+
+export interface CommandInvocation {
+  instance_id: string;
+  instance_name: string | null;
+  /** Pending, InProgress, Delayed, Success, Cancelled, TimedOut, Failed, Cancelling */
+  status: string;
+  status_details: string | null;
+  /** Only set once the invocation finished */
+  exit_code: number | null;
+  stdout: string | null;
+  stderr: string | null;
+  requested_at: number | null;
+}
+
+export interface RunCommandStatus {
+  status: string;
+  target_count: number;
+  completed_count: number;
+  error_count: number;
+}
+
+export interface CommandHistoryEntry {
+  command_id: string;
+  profile: string;
+  region: string;
+  input: RunCommandInput;
+  started_at: number;
+  finished_at: number | null;
+  /** Last known overall status */
+  status: RunCommandStatus | null;
+}
+
+/** Session Manager document to start instead of the default shell */
+export type SessionDocument =
+  | { type: 'interactive_command'; command: string }
+  | { type: 'non_interactive_command'; command: string }
+  | { type: 'custom'; name: string; parameters?: Record<string, string[]> };
+
+export interface SessionDocumentSummary {
+  name: string;
+  display_name: string | null;
+  /** "Amazon", the account ID, or the sharing account's ID */
+  owner: string | null;
+  document_version: string | null;
+  platform_types: string[];
+}
+
+export interface SessionDocumentParameter {
+  name: string;
+  /** String or StringList */
+  parameter_type: string | null;
+  description: string | null;
+  default_value: string | null;
+}
+
+export interface SessionDocumentDetail {
+  name: string;
+  description: string | null;
+  parameters: SessionDocumentParameter[];
+}
+
+/** Which Session Manager sessions to list */
+export type SessionListState = 'active' | 'history';
+
+/** A Session Manager session, started from this app or anywhere else */
+export interface SsmSessionRecord {
+  session_id: string;
+  /** Instance ID, or `ecs:...` for ECS Exec sessions */
+  target: string | null;
+  /** ARN of the IAM principal that started the session */
+  owner: string | null;
+  /** Connected, Connecting, Disconnected, Terminated, Terminating, Failed */
+  status: string | null;
+  document_name: string | null;
+  reason: string | null;
+  started_at: number | null;
+  ended_at: number | null;
+  s3_output_url: string | null;
+  cloudwatch_output_url: string | null;
+}
@@ -1,5 +1,8 @@
 // Terminal session types matching the Rust backend
 
+import type { SessionDocument } from './ssm';
+import type { ClientConnection, IamAuthTarget } from './tunnels';
+
 export type SessionType =
   | {
       type: 'ecs_exec';
@@ -14,6 +17,16 @@ export type SessionType =
       instance_id: string;
       profile: string;
       region: string;
+      /** Start a command or custom Session document instead of the default shell */
+      document?: SessionDocument | null;
+    }
+  | {
+      /** Real SSH through an SSM proxy, signing in with a temporary EC2 Instance Connect key */
+      type: 'instance_connect_ssh';
+      instance_id: string;
+      os_user: string;
+      profile: string;
+      region: string;
     }
   | {
       type: 'ssm_port_forwarding';
@@ -24,6 +37,26 @@ export type SessionType =
       profile: string;
       region: string;
     }
+  | {
+      /** Port forwarding through the SSM agent of an ECS container (ECS Exec must be enabled) */
+      type: 'ecs_port_forwarding';
+      cluster: string;
+      task: string;
+      container: string;
+      runtime_id: string;
+      local_port: number;
+      remote_port: number;
+      remote_host?: string;
+      profile: string;
+      region: string;
+    }
+  | {
+      /** Database client connected to the local end of a port forward */
+      type: 'database_client';
+      connection: ClientConnection;
+      /** Sign in with an RDS IAM authentication token instead of a password */
+      iam_auth?: IamAuthTarget | null;
+    }
   | {
       type: 'local';
     };
@@ -47,9 +80,13 @@ export interface CreateSessionInput {
   session_type: SessionType;
   title?: string;
   shell?: string;
+  /** For port forwarding: use the next free port when the local port is taken */
+  pick_free_port?: boolean;
 }
 
 export interface CreateSessionOutput {
   session_id: string;
   info: SessionInfo;
+  /** Local port actually bound, for port forwarding sessions */
+  local_port: number | null;
 }
@@ -0,0 +1,104 @@
+// Saved port-forward tunnel types matching the Rust backend
+
+export type TunnelTarget =
+  | { type: 'instance'; instance_id: string }
+  | {
+      type: 'ecs_task';
+      cluster: string;
+      task: string;
+      container: string;
+      runtime_id: string;
+    }
+  | {
+      /** Any running task of the service, resolved on every (re)connect */
+      type: 'ecs_service';
+      cluster: string;
+      service: string;
+      container: string;
+    };
+
+export interface TunnelDefinition {
+  id: string;
+  name: string;
+  profile: string;
+  region: string;
+  target: TunnelTarget;
+  local_port: number;
+  remote_port: number;
+  remote_host: string | null;
+  /** Start the tunnel when the app launches */
+  auto_start: boolean;
+  created_at: number;
+  updated_at: number;
+}
+
+export interface TunnelInput {
+  name: string;
+  profile: string;
+  region: string;
+  target: TunnelTarget;
+  local_port: number;
+  remote_port: number;
+  remote_host?: string | null;
+  auto_start?: boolean;
+}
+
+export type TunnelStatus = 'starting' | 'connected' | 'reconnecting' | 'stopped' | 'failed';
+
+export interface TunnelState {
+  tunnel_id: string;
+  name: string;
+  status: TunnelStatus;
+  local_port: number;
+  /** SSM target of the current connection */
+  ssm_target: string | null;
+  /** Consecutive failed attempts */
+  attempt: number;
+  last_error: string | null;
+  connected_at: number | null;
+  next_retry_at: number | null;
+}
+
+export interface PortOwner {
+  pid: number;
+  name: string;
+  command_line: string | null;
+}
+
+export interface LocalPortStatus {
+  port: number;
+  available: boolean;
+  /** Process holding the port, when it can be determined (Linux only) */
+  owner: PortOwner | null;
+  /** Next free port above port, when it is taken */
+  suggested_port: number | null;
+}
+
+export type DbClient = 'psql' | 'mysql' | 'redis_cli';
+
+export interface ClientConnection {
+  client: DbClient;
+  local_port: number;
+  username?: string | null;
+  database?: string | null;
+  /** Require TLS (without verifying the host name, which is localhost) */
+  tls?: boolean;
+}
+
+/** Real RDS endpoint an IAM authentication token is signed for */
+export interface IamAuthTarget {
+  profile: string;
+  region: string;
+  hostname: string;
+  port: number;
+}
+
+export interface ConnectionInfo {
+  client: DbClient;
+  /** Connection URI, without the password */
+  uri: string;
+  /** Shell command running the client, including the auth token when one was minted */
+  command_line: string;
+  auth_token: string | null;
+  auth_token_expires_at: number | null;
+}